@@ -0,0 +1,364 @@
+use crate::{
+    cell::Cell,
+    errors::ForthError,
+    forth_value::ForthValue,
+    operations::{
+        conditional::ConditionalOperation, forth_operation::ForthOperation, loops::LoopOperation,
+    },
+    words::{dictionary::WordsDictionary, word::ForthWord},
+};
+
+/// Enum that represents the instructions a body is compiled to. The jumps are resolved when the body is compiled, so they contain the position (in the body) of the next instruction to execute.
+///
+/// The different ones are:
+///
+/// - Literal: tuple that contains a Cell number to push.
+/// - FloatLiteral: tuple that contains a f64 number to push to the float stack (only with the "float" feature).
+/// - Operation: tuple that contains a ForthOperation that does not change the flow of the body.
+/// - Call: tuple that contains the index (in the dictionary) of the definition of a word to execute.
+/// - CallByName: tuple that contains the name of a word that was not defined when the body was compiled, so it is looked up when it is executed.
+/// - Jump: tuple that contains the position to jump to ('ELSE', 'REPEAT' and 'AGAIN').
+/// - JumpIfZero: tuple that contains the position to jump to if the value popped from the stack is false ('IF', 'WHILE' and 'UNTIL').
+/// - Do: starts a counted loop, taking the limit and the initial index from the stack.
+/// - Loop: tuple that contains the position of the first instruction of the loop, where it jumps back until the limit is reached.
+/// - PlusLoop: like Loop, but increments the index by the value on top of the stack.
+/// - Exit: stops the execution of the body ('EXIT').
+///
+
+#[derive(Debug)]
+pub enum Instruction {
+    Literal(Cell),
+    #[cfg(feature = "float")]
+    FloatLiteral(f64),
+    Operation(ForthOperation),
+    Call(usize),
+    CallByName(String),
+    Jump(usize),
+    JumpIfZero(usize),
+    Do,
+    Loop(usize),
+    PlusLoop(usize),
+    Exit,
+}
+
+/// Enum that represents a control structure whose end has not been compiled yet. It keeps the positions needed to resolve its jumps.
+///
+/// The different ones are:
+///
+/// - If: contains the position of the JumpIfZero of 'IF'.
+/// - Else: contains the position of the Jump of 'ELSE'.
+/// - Do: contains the position of the first instruction after 'DO'.
+/// - Begin: contains the position of the first instruction after 'BEGIN'.
+/// - While: contains the position of the first instruction after 'BEGIN' and the position of the JumpIfZero of 'WHILE'.
+///
+
+#[derive(Debug)]
+enum ControlStructure {
+    If(usize),
+    Else(usize),
+    Do(usize),
+    Begin(usize),
+    While { begin: usize, exit: usize },
+}
+
+/// Function that returns true if the value received by parameter opens a control structure ('IF', 'DO' or 'BEGIN').
+pub fn opens_control_structure(value: &ForthValue) -> bool {
+    matches!(
+        value,
+        ForthValue::Operation(
+            ForthOperation::Conditional(ConditionalOperation::If)
+                | ForthOperation::Loop(LoopOperation::Do | LoopOperation::Begin)
+        )
+    )
+}
+
+/// Function that returns true if the value received by parameter closes a control structure ('THEN', 'LOOP', '+LOOP', 'UNTIL', 'REPEAT' or 'AGAIN').
+pub fn closes_control_structure(value: &ForthValue) -> bool {
+    matches!(
+        value,
+        ForthValue::Operation(
+            ForthOperation::Conditional(ConditionalOperation::Then)
+                | ForthOperation::Loop(
+                    LoopOperation::Loop
+                        | LoopOperation::PlusLoop
+                        | LoopOperation::Until
+                        | LoopOperation::Repeat
+                        | LoopOperation::Again
+                )
+        )
+    )
+}
+
+/// Function that returns how many control structures are left open in the values received by parameter.
+/// It is used to know when a control structure typed outside a word definition has been completely read, so it can be compiled and executed.
+pub fn control_nesting(values: &[ForthValue]) -> usize {
+    let mut depth: usize = 0;
+    for val in values {
+        if opens_control_structure(val) {
+            depth += 1;
+        } else if closes_control_structure(val) {
+            depth = depth.saturating_sub(1);
+        }
+    }
+    depth
+}
+
+/// Function that compiles the values received by parameter into the instructions of a body.
+/// The words are resolved to the index of their current definition in the WordsDictionary received by parameter (so redefining them later does not change the body),
+/// or kept by their name if they are not defined yet. If word_name is received, the body is the definition of that word, which is added to the dictionary after the current ones:
+/// 'RECURSE' is compiled as a call to it, and so is its own name if allow_self_calls is true (otherwise the name refers to its previous definition).
+/// Returns invalid-word if an 'ELSE' or 'THEN' does not belong to an 'IF', an 'IF' is not closed, 'RECURSE' is used outside a word definition,
+/// or the word uses its own name without a previous definition (and allow_self_calls is false),
+/// and invalid-loop if a loop word does not belong to its loop or a loop is not closed.
+pub fn compile_definition(
+    values: Vec<ForthValue>,
+    dictionary: &WordsDictionary,
+    word_name: Option<&str>,
+    allow_self_calls: bool,
+) -> Result<Vec<Instruction>, ForthError> {
+    let mut code = Vec::with_capacity(values.len());
+    let mut open_structures = Vec::new();
+    for val in values {
+        match val {
+            ForthValue::Number(n) => code.push(Instruction::Literal(n)),
+            ForthValue::DoubleNumber(first, second) | ForthValue::String(first, second) => {
+                code.push(Instruction::Literal(first));
+                code.push(Instruction::Literal(second));
+            }
+            #[cfg(feature = "float")]
+            ForthValue::FloatNumber(f) => code.push(Instruction::FloatLiteral(f)),
+            ForthValue::Operation(ForthOperation::Conditional(op)) => {
+                compile_conditional(op, &mut code, &mut open_structures)?
+            }
+            ForthValue::Operation(ForthOperation::Loop(op)) => {
+                compile_loop(op, &mut code, &mut open_structures)?
+            }
+            ForthValue::Operation(op) => code.push(Instruction::Operation(op)),
+            ForthValue::Word(ForthWord::Recurse) => match word_name {
+                Some(_) => code.push(Instruction::Call(dictionary.next_index())),
+                None => return Err(ForthError::InvalidWord),
+            },
+            ForthValue::Word(ForthWord::Start(name)) => code.push(compile_word_call(
+                name,
+                dictionary,
+                word_name,
+                allow_self_calls,
+            )?),
+            ForthValue::Word(_) => return Err(ForthError::InvalidWord),
+        }
+    }
+    match open_structures.last() {
+        None => Ok(code),
+        Some(ControlStructure::If(_) | ControlStructure::Else(_)) => Err(ForthError::InvalidWord),
+        Some(_) => Err(ForthError::InvalidLoop),
+    }
+}
+
+fn compile_word_call(
+    name: String,
+    dictionary: &WordsDictionary,
+    word_name: Option<&str>,
+    allow_self_calls: bool,
+) -> Result<Instruction, ForthError> {
+    let self_call = word_name == Some(name.as_str());
+    if allow_self_calls && self_call {
+        return Ok(Instruction::Call(dictionary.next_index()));
+    }
+    match dictionary.find_word(&name) {
+        Some(index) => Ok(Instruction::Call(index)),
+        None if self_call => Err(ForthError::InvalidWord),
+        None => Ok(Instruction::CallByName(name)),
+    }
+}
+
+fn compile_conditional(
+    op: ConditionalOperation,
+    code: &mut Vec<Instruction>,
+    open_structures: &mut Vec<ControlStructure>,
+) -> Result<(), ForthError> {
+    match op {
+        ConditionalOperation::If => {
+            open_structures.push(ControlStructure::If(code.len()));
+            code.push(Instruction::JumpIfZero(0));
+        }
+        ConditionalOperation::Else => {
+            let Some(ControlStructure::If(if_position)) = open_structures.pop() else {
+                return Err(ForthError::InvalidWord);
+            };
+            open_structures.push(ControlStructure::Else(code.len()));
+            code.push(Instruction::Jump(0));
+            resolve_jump(code, if_position);
+        }
+        ConditionalOperation::Then => match open_structures.pop() {
+            Some(ControlStructure::If(position) | ControlStructure::Else(position)) => {
+                resolve_jump(code, position)
+            }
+            _ => return Err(ForthError::InvalidWord),
+        },
+    }
+    Ok(())
+}
+
+fn compile_loop(
+    op: LoopOperation,
+    code: &mut Vec<Instruction>,
+    open_structures: &mut Vec<ControlStructure>,
+) -> Result<(), ForthError> {
+    match op {
+        LoopOperation::Do => {
+            code.push(Instruction::Do);
+            open_structures.push(ControlStructure::Do(code.len()));
+        }
+        LoopOperation::Loop | LoopOperation::PlusLoop => {
+            let Some(ControlStructure::Do(body_start)) = open_structures.pop() else {
+                return Err(ForthError::InvalidLoop);
+            };
+            code.push(match op {
+                LoopOperation::Loop => Instruction::Loop(body_start),
+                _ => Instruction::PlusLoop(body_start),
+            });
+        }
+        LoopOperation::Begin => open_structures.push(ControlStructure::Begin(code.len())),
+        LoopOperation::Until | LoopOperation::Again => {
+            let Some(ControlStructure::Begin(begin)) = open_structures.pop() else {
+                return Err(ForthError::InvalidLoop);
+            };
+            code.push(match op {
+                LoopOperation::Until => Instruction::JumpIfZero(begin),
+                _ => Instruction::Jump(begin),
+            });
+        }
+        LoopOperation::While => {
+            let Some(ControlStructure::Begin(begin)) = open_structures.pop() else {
+                return Err(ForthError::InvalidLoop);
+            };
+            open_structures.push(ControlStructure::While {
+                begin,
+                exit: code.len(),
+            });
+            code.push(Instruction::JumpIfZero(0));
+        }
+        LoopOperation::Repeat => {
+            let Some(ControlStructure::While { begin, exit }) = open_structures.pop() else {
+                return Err(ForthError::InvalidLoop);
+            };
+            code.push(Instruction::Jump(begin));
+            resolve_jump(code, exit);
+        }
+        LoopOperation::Exit => code.push(Instruction::Exit),
+        LoopOperation::Index | LoopOperation::OuterIndex | LoopOperation::Leave => {
+            code.push(Instruction::Operation(ForthOperation::Loop(op)))
+        }
+    }
+    Ok(())
+}
+
+/// Function that sets the position after the last instruction compiled as the target of the jump at the position received by parameter.
+fn resolve_jump(code: &mut [Instruction], position: usize) {
+    let target = code.len();
+    if let Some(Instruction::Jump(jump_target) | Instruction::JumpIfZero(jump_target)) =
+        code.get_mut(position)
+    {
+        *jump_target = target;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Instruction, compile_definition};
+    use crate::{
+        cell::CellConfig,
+        errors::ForthError,
+        tokens::{parse_token, tokenize},
+        words::dictionary::WordsDictionary,
+    };
+
+    fn compile(code: &str, dictionary: &WordsDictionary) -> Result<Vec<Instruction>, ForthError> {
+        let values = tokenize(code)
+            .iter()
+            .map(|token| parse_token(token, dictionary, &CellConfig::default(), 10).unwrap())
+            .collect();
+        compile_definition(values, dictionary, Some("F"), false)
+    }
+
+    #[test]
+    fn test_if_else_jumps() {
+        let dict = WordsDictionary::new();
+        let code = compile("if 1 else 2 then 3", &dict).unwrap();
+
+        assert!(matches!(
+            code.as_slice(),
+            [
+                Instruction::JumpIfZero(3),
+                Instruction::Literal(1),
+                Instruction::Jump(4),
+                Instruction::Literal(2),
+                Instruction::Literal(3),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_loop_jumps() {
+        let dict = WordsDictionary::new();
+        let code = compile("begin dup while 1 - repeat 3 0 do loop", &dict).unwrap();
+
+        assert!(matches!(
+            code.as_slice(),
+            [
+                Instruction::Operation(_),
+                Instruction::JumpIfZero(5),
+                Instruction::Literal(1),
+                Instruction::Operation(_),
+                Instruction::Jump(0),
+                Instruction::Literal(3),
+                Instruction::Literal(0),
+                Instruction::Do,
+                Instruction::Loop(8),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_words_are_not_inlined() {
+        let mut dict = WordsDictionary::new();
+        dict.add_word(
+            "FOO",
+            vec![Instruction::Literal(1), Instruction::Literal(2)],
+        );
+        let code = compile("foo foo bar recurse", &dict).unwrap();
+
+        assert!(matches!(
+            code.as_slice(),
+            [
+                Instruction::Call(0),
+                Instruction::Call(0),
+                Instruction::CallByName(name),
+                Instruction::Call(1),
+            ] if name == "BAR"
+        ));
+    }
+
+    #[test]
+    fn test_unbalanced_structures() {
+        let dict = WordsDictionary::new();
+
+        assert_eq!(
+            compile("1 if 2", &dict).err(),
+            Some(ForthError::InvalidWord)
+        );
+        assert_eq!(compile("then", &dict).err(), Some(ForthError::InvalidWord));
+        assert_eq!(
+            compile("begin if again", &dict).err(),
+            Some(ForthError::InvalidLoop)
+        );
+        assert_eq!(
+            compile("3 0 do", &dict).err(),
+            Some(ForthError::InvalidLoop)
+        );
+        assert_eq!(
+            compile("if loop then", &dict).err(),
+            Some(ForthError::InvalidLoop)
+        );
+    }
+}
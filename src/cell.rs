@@ -0,0 +1,303 @@
+use crate::errors::ForthError;
+
+/// Type of the values kept in the Stack, the DataMemory and the ReturnStack. It is wide enough for every CellWidth,
+/// and the values are kept in the range of the configured one (see CellConfig).
+pub type Cell = i64;
+
+/// Enum that represents the width of the cells.
+///
+/// The different ones are:
+///
+/// - Bits16: cells of 16 bits (from -32768 to 32767). This is the default, as in Forth-79.
+/// - Bits32: cells of 32 bits.
+/// - Bits64: cells of 64 bits.
+///
+
+#[derive(Debug, Default, PartialEq)]
+pub enum CellWidth {
+    #[default]
+    Bits16,
+    Bits32,
+    Bits64,
+}
+
+/// Enum that represents what happens when the result of an operation (or a number literal) does not fit in a cell.
+///
+/// The different ones are:
+///
+/// - Wrapping: the result wraps around (as in real Forth). This is the default.
+/// - Saturating: the result is clamped to the smallest or greatest value of the cell.
+/// - Error: the operation fails with overflow.
+///
+
+#[derive(Debug, Default, PartialEq)]
+pub enum OverflowPolicy {
+    #[default]
+    Wrapping,
+    Saturating,
+    Error,
+}
+
+/// Enum that represents how a division rounds its quotient when it is not exact.
+///
+/// The different ones are:
+///
+/// - Symmetric: the quotient is rounded towards zero, so the remainder has the sign of the dividend (-7 2 /MOD gives -1 -3). This is the default.
+/// - Floored: the quotient is rounded towards negative infinity, so the remainder has the sign of the divisor (-7 2 /MOD gives 1 -4).
+///
+
+#[derive(Debug, Default, PartialEq)]
+pub enum DivisionMode {
+    #[default]
+    Symmetric,
+    Floored,
+}
+
+/// This struct groups the width of the cells, the policy used when a value does not fit in them and how the divisions are rounded.
+#[derive(Debug, Default)]
+pub struct CellConfig {
+    pub width: CellWidth,
+    pub overflow: OverflowPolicy,
+    pub division: DivisionMode,
+}
+
+impl CellConfig {
+    /// Function that returns the amount of bits of a cell.
+    pub fn bits(&self) -> u32 {
+        match self.width {
+            CellWidth::Bits16 => 16,
+            CellWidth::Bits32 => 32,
+            CellWidth::Bits64 => 64,
+        }
+    }
+
+    /// Function that returns the amount of bytes of a cell (used to know how many cells fit in a size in bytes).
+    pub fn bytes(&self) -> usize {
+        self.bits() as usize / 8
+    }
+
+    /// Function that returns the smallest value a cell can hold.
+    pub fn min(&self) -> Cell {
+        Cell::MIN >> (Cell::BITS - self.bits())
+    }
+
+    /// Function that returns the greatest value a cell can hold.
+    pub fn max(&self) -> Cell {
+        Cell::MAX >> (Cell::BITS - self.bits())
+    }
+
+    /// Function that converts the value received by parameter (computed with a wider type, so it can not overflow) to a cell, applying the OverflowPolicy if it does not fit.
+    /// Returns overflow if it does not fit and the policy is Error.
+    pub fn fit(&self, value: i128) -> Result<Cell, ForthError> {
+        let (min, max) = (i128::from(self.min()), i128::from(self.max()));
+        if (min..=max).contains(&value) {
+            return Ok(value as Cell);
+        }
+        match self.overflow {
+            OverflowPolicy::Wrapping => Ok(self.wrap(value)),
+            OverflowPolicy::Saturating => Ok(value.clamp(min, max) as Cell),
+            OverflowPolicy::Error => Err(ForthError::Overflow),
+        }
+    }
+
+    /// Function that converts the value received by parameter to a cell keeping only its lowest bits (as the bitwise operations do), no matter the OverflowPolicy.
+    pub fn wrap(&self, value: i128) -> Cell {
+        let shift = i128::BITS - self.bits();
+        ((value << shift) >> shift) as Cell
+    }
+
+    /// Function that returns the value received by parameter read as an unsigned cell (-1 is the greatest unsigned value), used by the unsigned operations.
+    pub fn unsigned(&self, value: Cell) -> u128 {
+        (value as u128) & (u128::MAX >> (u128::BITS - self.bits()))
+    }
+
+    /// Function that divides the dividend received by parameter by the divisor, rounding the quotient by the DivisionMode.
+    /// Returns a tuple with the remainder and the quotient (in the order Forth leaves them), or division-by-zero if the divisor is 0.
+    pub fn divide(&self, dividend: i128, divisor: i128) -> Result<(i128, i128), ForthError> {
+        if divisor == 0 {
+            return Err(ForthError::DivisionByZero);
+        }
+        let (remainder, quotient) = (dividend % divisor, dividend / divisor);
+        if self.division == DivisionMode::Floored
+            && remainder != 0
+            && (remainder < 0) != (divisor < 0)
+        {
+            return Ok((remainder + divisor, quotient - 1));
+        }
+        Ok((remainder, quotient))
+    }
+
+    /// Function that splits the double-cell number received by parameter into its low and high cells (in the order they are pushed to the stack).
+    /// A double-cell number has twice the bits of a cell, and it wraps around if it does not fit in them.
+    pub fn split_double(&self, value: i128) -> (Cell, Cell) {
+        (self.wrap(value), self.wrap(value >> self.bits()))
+    }
+
+    /// Function that joins the low and high cells received by parameter into the double-cell number they represent.
+    pub fn join_double(&self, low: Cell, high: Cell) -> i128 {
+        (i128::from(high) << self.bits()) | self.unsigned(low) as i128
+    }
+
+    /// Function that returns the double-cell number received by parameter read as unsigned (-1 is the greatest unsigned value), used by the unsigned operations.
+    pub fn unsigned_double(&self, value: i128) -> u128 {
+        (value as u128) & (u128::MAX >> (u128::BITS - 2 * self.bits()))
+    }
+
+    /// Function that parses a number literal received by parameter as &str in the number base received by parameter, fitting it in a cell.
+    /// The literal can also choose its own base with a prefix ('$' for hexadecimal, '#' for decimal and '%' for binary, as in '$FF', '#10' or '%1010'),
+    /// or be a char between single quotes ('c'), whose value is its code.
+    /// Returns None if the token is not a number, or overflow if it does not fit and the policy is Error.
+    pub fn parse_number(&self, token: &str, base: u32) -> Option<Result<Cell, ForthError>> {
+        let mut chars = token.chars();
+        if let (Some('\''), Some(c), Some('\''), None) =
+            (chars.next(), chars.next(), chars.next(), chars.next())
+        {
+            return Some(self.fit(c as i128));
+        }
+        parse_wide_number(token, base).map(|value| self.fit(value))
+    }
+
+    /// Function that parses a double-cell number literal received by parameter as &str, which is a number (as parse_number reads them) followed by a '.' (as '123.' or '$FFFF.').
+    /// Returns its low and high cells (see split_double), or None if the token is not a double-cell number.
+    pub fn parse_double(&self, token: &str, base: u32) -> Option<(Cell, Cell)> {
+        let digits = token.strip_suffix('.')?;
+        parse_wide_number(digits, base).map(|value| self.split_double(value))
+    }
+}
+
+/// Function that parses the number received by parameter as &str in the number base received by parameter (or the one of its prefix), without fitting it in a cell.
+fn parse_wide_number(token: &str, base: u32) -> Option<i128> {
+    let (digits, base) = match token.split_at_checked(1) {
+        Some(("$", digits)) => (digits, 16),
+        Some(("#", digits)) => (digits, 10),
+        Some(("%", digits)) => (digits, 2),
+        _ => (token, base),
+    };
+    i128::from_str_radix(digits, base).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CellConfig, CellWidth, DivisionMode, OverflowPolicy};
+    use crate::errors::ForthError;
+
+    #[test]
+    fn test_limits() {
+        let cell = CellConfig::default();
+        assert_eq!((cell.min(), cell.max(), cell.bytes()), (-32768, 32767, 2));
+
+        let cell = CellConfig {
+            width: CellWidth::Bits32,
+            ..Default::default()
+        };
+        assert_eq!(
+            (cell.min(), cell.max(), cell.bytes()),
+            (-(1 << 31), (1 << 31) - 1, 4)
+        );
+
+        let cell = CellConfig {
+            width: CellWidth::Bits64,
+            ..Default::default()
+        };
+        assert_eq!(
+            (cell.min(), cell.max(), cell.bytes()),
+            (i64::MIN, i64::MAX, 8)
+        );
+    }
+
+    #[test]
+    fn test_overflow_policies() {
+        let wrapping = CellConfig::default();
+        assert_eq!(wrapping.fit(32768), Ok(-32768));
+        assert_eq!(wrapping.fit(-32769), Ok(32767));
+        assert_eq!(wrapping.fit(65536 + 5), Ok(5));
+
+        let saturating = CellConfig {
+            overflow: OverflowPolicy::Saturating,
+            ..Default::default()
+        };
+        assert_eq!(saturating.fit(40000), Ok(32767));
+        assert_eq!(saturating.fit(-40000), Ok(-32768));
+
+        let error = CellConfig {
+            overflow: OverflowPolicy::Error,
+            ..Default::default()
+        };
+        assert_eq!(error.fit(32767), Ok(32767));
+        assert_eq!(error.fit(32768), Err(ForthError::Overflow));
+    }
+
+    #[test]
+    fn test_wrap_and_unsigned() {
+        let cell = CellConfig {
+            overflow: OverflowPolicy::Error,
+            ..Default::default()
+        };
+        assert_eq!(cell.wrap(1 << 15), -32768);
+        assert_eq!(cell.wrap(1 << 16), 0);
+        assert_eq!(cell.unsigned(-1), 65535);
+        assert_eq!(cell.unsigned(5), 5);
+    }
+
+    #[test]
+    fn test_double_cells() {
+        let cell = CellConfig::default();
+        assert_eq!(cell.split_double(100000), (-31072, 1));
+        assert_eq!(cell.join_double(-31072, 1), 100000);
+        assert_eq!(cell.split_double(-1), (-1, -1));
+        assert_eq!(cell.join_double(-1, -1), -1);
+        assert_eq!(cell.unsigned_double(-1), (1 << 32) - 1);
+        assert_eq!(cell.parse_double("100000.", 10), Some((-31072, 1)));
+        assert_eq!(cell.parse_double("-1.", 10), Some((-1, -1)));
+        assert_eq!(cell.parse_double("100000", 10), None);
+
+        let cell = CellConfig {
+            width: CellWidth::Bits64,
+            ..Default::default()
+        };
+        assert_eq!(cell.split_double(i128::MIN), (0, i64::MIN));
+        assert_eq!(cell.join_double(0, i64::MIN), i128::MIN);
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let cell = CellConfig::default();
+        assert_eq!(cell.parse_number("-12", 10), Some(Ok(-12)));
+        assert_eq!(cell.parse_number("70000", 10), Some(Ok(4464)));
+        assert_eq!(cell.parse_number("foo", 10), None);
+        assert_eq!(cell.parse_number("ff", 16), Some(Ok(255)));
+        assert_eq!(cell.parse_number("ff", 10), None);
+        assert_eq!(cell.parse_number("777", 8), Some(Ok(511)));
+    }
+
+    #[test]
+    fn test_parse_prefixed_number() {
+        let cell = CellConfig::default();
+        assert_eq!(cell.parse_number("$FF", 10), Some(Ok(255)));
+        assert_eq!(cell.parse_number("$-ff", 10), Some(Ok(-255)));
+        assert_eq!(cell.parse_number("#10", 16), Some(Ok(10)));
+        assert_eq!(cell.parse_number("%1010", 10), Some(Ok(10)));
+        assert_eq!(cell.parse_number("'A'", 10), Some(Ok(65)));
+        assert_eq!(cell.parse_number("$", 10), None);
+        assert_eq!(cell.parse_number("%12", 10), None);
+        assert_eq!(cell.parse_number("'AB'", 10), None);
+    }
+
+    #[test]
+    fn test_division_modes() {
+        let symmetric = CellConfig::default();
+        assert_eq!(symmetric.divide(7, 2), Ok((1, 3)));
+        assert_eq!(symmetric.divide(-7, 2), Ok((-1, -3)));
+        assert_eq!(symmetric.divide(7, -2), Ok((1, -3)));
+        assert_eq!(symmetric.divide(1, 0), Err(ForthError::DivisionByZero));
+
+        let floored = CellConfig {
+            division: DivisionMode::Floored,
+            ..Default::default()
+        };
+        assert_eq!(floored.divide(7, 2), Ok((1, 3)));
+        assert_eq!(floored.divide(-7, 2), Ok((1, -4)));
+        assert_eq!(floored.divide(7, -2), Ok((-1, -4)));
+        assert_eq!(floored.divide(-6, 2), Ok((0, -3)));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    data_memory::DataMemory,
+    file_table::{DEFAULT_FILE_ROOT, FileTable},
+    input_source::{InputSource, StdinInput},
+    interpreter::{DEFAULT_MAX_CALL_DEPTH, DEFAULT_MEMORY_SIZE, DEFAULT_RETURN_STACK_SIZE},
+    operations::output::OutputMode,
+    output_sink::{OutputSink, StdoutOutput},
+    return_stack::ReturnStack,
+};
+
+/// This struct groups everything (apart from the Stack) that the execution of the values may use or change, so it can be passed as a whole.
+/// It contains:
+/// - memory: the DataMemory where variables and allotted cells are kept.
+/// - return_stack: the ReturnStack where values, loops and word calls are kept.
+/// - output: the OutputSink where the printed text is written.
+/// - input: the InputSource where the chars read by the program come from.
+/// - files: the FileTable where the files opened by the program are kept.
+/// - output_mode: the OutputMode used to format the printed text.
+/// - float_stack: the FloatStack where the floating-point values are kept (only with the "float" feature).
+#[derive(Debug)]
+pub struct ForthContext {
+    pub memory: DataMemory,
+    pub return_stack: ReturnStack,
+    pub output: Box<dyn OutputSink>,
+    pub input: Box<dyn InputSource>,
+    pub files: FileTable,
+    pub output_mode: OutputMode,
+    #[cfg(feature = "float")]
+    pub float_stack: crate::float_stack::FloatStack,
+}
+
+impl Default for ForthContext {
+    fn default() -> Self {
+        ForthContext {
+            memory: DataMemory::new(DEFAULT_MEMORY_SIZE),
+            return_stack: ReturnStack::new(DEFAULT_RETURN_STACK_SIZE, DEFAULT_MAX_CALL_DEPTH),
+            output: Box::new(StdoutOutput),
+            input: Box::new(StdinInput),
+            files: FileTable::new(DEFAULT_FILE_ROOT),
+            output_mode: OutputMode::default(),
+            #[cfg(feature = "float")]
+            float_stack: crate::float_stack::FloatStack::new(
+                crate::interpreter::DEFAULT_FLOAT_STACK_SIZE,
+            ),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    cell::{Cell, CellConfig},
+    errors::ForthError,
+};
+
+/// Address of the BASE variable. It is the greatest address a cell of 16 bits can hold, so it is never one of the allotted cells.
+pub const BASE_ADDRESS: Cell = i16::MAX as Cell;
+
+/// Address of the SPAN variable, the cell just before the one of BASE.
+pub const SPAN_ADDRESS: Cell = BASE_ADDRESS - 1;
+
+/// Amount of transient buffers, where the strings of 'S"' typed outside a word definition and a control structure are kept. They are used in turns, so the last two strings can be used at the same time.
+pub const TRANSIENT_BUFFERS: usize = 2;
+
+/// Max amount of chars of a string kept in a transient buffer.
+pub const TRANSIENT_SIZE: Cell = 256;
+
+/// Address of the first transient buffer. The buffers are just before the cell of SPAN, so they are never allotted cells.
+pub const TRANSIENT_ADDRESS: Cell = SPAN_ADDRESS - TRANSIENT_BUFFERS as Cell * TRANSIENT_SIZE;
+
+/// Number base used to parse and print the numbers when the program starts.
+pub const DEFAULT_BASE: Cell = 10;
+
+/// Function that returns the address that is the offset received by parameter after the address received by parameter.
+/// Returns invalid-address if it does not fit in a Cell (so it can not be an allocated cell).
+pub fn offset_address(address: Cell, offset: Cell) -> Result<Cell, ForthError> {
+    address
+        .checked_add(offset)
+        .ok_or(ForthError::InvalidAddress)
+}
+
+/// Function that returns the position in the transient buffers of the address received by parameter, or None if it is not one of their addresses.
+fn transient_position(address: Cell) -> Option<usize> {
+    (TRANSIENT_ADDRESS..SPAN_ADDRESS)
+        .contains(&address)
+        .then(|| (address - TRANSIENT_ADDRESS) as usize)
+}
+
+/// Function that returns the position in the allotted cells of the address received by parameter. Returns invalid-address if it is negative.
+fn cell_position(address: Cell) -> Result<usize, ForthError> {
+    usize::try_from(address).map_err(|_| ForthError::InvalidAddress)
+}
+
+/// This struct is the data space of the program, where variables and allotted cells are kept.
+/// It contains the cells (as pub so they can be read by other modules) of Cell values, addressed by their position.
+/// The cells from 0 to 'here' (excluded) are the allocated ones, and the max_cells field is used to bound the space by the size received in the program input.
+/// It also contains the value of the BASE variable (the number base used to parse and print the numbers), which is accessed by its own address (BASE_ADDRESS),
+/// and the one of the SPAN variable (the amount of chars received by the last 'EXPECT'), accessed by SPAN_ADDRESS.
+/// The transient buffers (transient) are accessed from TRANSIENT_ADDRESS, and next_transient is the one the next string is kept in.
+#[derive(Debug)]
+pub struct DataMemory {
+    pub cells: Vec<Cell>,
+    max_cells: usize,
+    base: Cell,
+    span: Cell,
+    transient: Vec<Cell>,
+    next_transient: usize,
+}
+
+impl DataMemory {
+    /// Function used to build the structure with cells of 16 bits. Receives a size in bytes that sets the max_cells of the DataMemory.
+    pub fn new(size: usize) -> Self {
+        Self::with_cells(size, &CellConfig::default())
+    }
+
+    /// Function used to build the structure. Receives a size in bytes that sets the max_cells of the DataMemory (by the bytes of each cell of the CellConfig received by parameter),
+    /// which can not reach the addresses of the transient buffers, SPAN and BASE, the greatest ones a cell of 16 bits can hold.
+    pub fn with_cells(size: usize, cell: &CellConfig) -> Self {
+        let max_cells = (size / cell.bytes()).min(TRANSIENT_ADDRESS as usize);
+        DataMemory {
+            cells: Vec::new(),
+            max_cells,
+            base: DEFAULT_BASE,
+            span: 0,
+            transient: vec![0; TRANSIENT_BUFFERS * TRANSIENT_SIZE as usize],
+            next_transient: 0,
+        }
+    }
+
+    /// Function that returns the number base used to parse and print the numbers (the value of the BASE variable).
+    pub fn base(&self) -> u32 {
+        self.base as u32
+    }
+
+    /// Function that returns the address of the next free cell.
+    pub fn here(&self) -> Cell {
+        self.cells.len() as Cell
+    }
+
+    /// Function used to reserve the amount of cells received by parameter (or release them, if it is negative), initialized as 0.
+    /// Returns memory-overflow if the data space is exceeded, or invalid-address if more cells than the allocated ones are released.
+    pub fn allot(&mut self, amount: Cell) -> Result<(), ForthError> {
+        let new_len = self.cells.len() as i128 + i128::from(amount);
+        if new_len < 0 {
+            return Err(ForthError::InvalidAddress);
+        }
+        if new_len as usize > self.max_cells {
+            return Err(ForthError::MemoryOverflow);
+        }
+        self.cells.resize(new_len as usize, 0);
+        Ok(())
+    }
+
+    /// Function used to reserve one cell and store the value received by parameter in it. Returns the address of that cell.
+    pub fn comma(&mut self, value: Cell) -> Result<Cell, ForthError> {
+        let address = self.here();
+        self.allot(1)?;
+        self.store(address, value)?;
+        Ok(address)
+    }
+
+    /// Function used to reserve one cell for each char of the text received by parameter and store their codes in them. Returns the address of the first one.
+    pub fn store_string(&mut self, text: &str) -> Result<Cell, ForthError> {
+        let address = self.here();
+        let codes: Vec<Cell> = text.chars().map(|c| c as Cell).collect();
+        self.allot(codes.len() as Cell)?;
+        self.cells[address as usize..].copy_from_slice(&codes);
+        Ok(address)
+    }
+
+    /// Function used to keep the text received by parameter in the next transient buffer (one cell for each char code), without reserving any cell.
+    /// Returns the address of its first char, or memory-overflow if the text does not fit in a transient buffer.
+    pub fn store_transient(&mut self, text: &str) -> Result<Cell, ForthError> {
+        let codes: Vec<Cell> = text.chars().map(|c| c as Cell).collect();
+        if codes.len() > TRANSIENT_SIZE as usize {
+            return Err(ForthError::MemoryOverflow);
+        }
+        let start = self.next_transient * TRANSIENT_SIZE as usize;
+        self.transient[start..start + codes.len()].copy_from_slice(&codes);
+        self.next_transient = (self.next_transient + 1) % TRANSIENT_BUFFERS;
+        Ok(TRANSIENT_ADDRESS + start as Cell)
+    }
+
+    /// Function that returns the cells the address received by parameter belongs to (the transient buffers, or the allotted cells), and its position in them.
+    /// Returns invalid-address if the address is negative.
+    fn locate(&self, address: Cell) -> Result<(&[Cell], usize), ForthError> {
+        match transient_position(address) {
+            Some(position) => Ok((&self.transient, position)),
+            None => Ok((&self.cells, cell_position(address)?)),
+        }
+    }
+
+    /// Function to get the values of the amount of cells (length) received by parameter, starting at the address received by parameter.
+    /// Returns invalid-address if any of them is not allocated, or the length is negative.
+    pub fn fetch_range(&self, address: Cell, length: Cell) -> Result<&[Cell], ForthError> {
+        let (cells, start) = self.locate(address)?;
+        let length = usize::try_from(length).map_err(|_| ForthError::InvalidAddress)?;
+        start
+            .checked_add(length)
+            .and_then(|end| cells.get(start..end))
+            .ok_or(ForthError::InvalidAddress)
+    }
+
+    /// Function to get the value of the cell at the address received by parameter. Returns invalid-address if the cell is not allocated.
+    pub fn fetch(&self, address: Cell) -> Result<Cell, ForthError> {
+        if address == BASE_ADDRESS {
+            return Ok(self.base);
+        }
+        if address == SPAN_ADDRESS {
+            return Ok(self.span);
+        }
+        let (cells, position) = self.locate(address)?;
+        cells
+            .get(position)
+            .copied()
+            .ok_or(ForthError::InvalidAddress)
+    }
+
+    /// Function to set the value of the cell at the address received by parameter. Returns invalid-address if the cell is not allocated,
+    /// or invalid-base if the address is the one of BASE and the value is not a number base between 2 and 36.
+    pub fn store(&mut self, address: Cell, value: Cell) -> Result<(), ForthError> {
+        if address == BASE_ADDRESS {
+            if !(2..=36).contains(&value) {
+                return Err(ForthError::InvalidBase);
+            }
+            self.base = value;
+            return Ok(());
+        }
+        if address == SPAN_ADDRESS {
+            self.span = value;
+            return Ok(());
+        }
+        let cell = match transient_position(address) {
+            Some(position) => self.transient.get_mut(position),
+            None => self.cells.get_mut(cell_position(address)?),
+        }
+        .ok_or(ForthError::InvalidAddress)?;
+        *cell = value;
+        Ok(())
+    }
+}
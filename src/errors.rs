@@ -4,9 +4,21 @@ use std::fmt::Display;
 Los distintos son:
 - StackUnderflow: when an operation attempts to pop an element from an empty stack.
 - StackOverflow: when an operation attempts to push an element onto a stack that is at its maximum memory capacity.
-- InvalidWord: when trying to define an invalid word, for example: : 1 1 ;.
+- InvalidWord: when trying to define an invalid word, for example: : 1 1 ;, or a conditional word (ELSE, THEN) is used without its 'IF'.
 - DivisionByZero: when trying to divide by zero.
 - UnknownWord: when the interpreter cannot find the definition of the word being evaluated.
+- ReturnStackUnderflow: when an operation attempts to take a value from the return stack and there is none available (R>, R@).
+- ReturnStackOverflow: when the return stack can not hold more values, loops or word calls.
+- CallDepthExceeded: when too many words are being executed at the same time, for example by a recursion that never ends.
+- InvalidAddress: when a memory operation accesses a cell that is not allocated in the data memory.
+- MemoryOverflow: when trying to allot more cells than the data memory can hold, or a string typed outside a word definition and a control structure does not fit in a transient buffer.
+- Overflow: when the result of an operation or a number does not fit in a cell, and the overflow policy is to raise an error.
+- InvalidBase: when trying to store in BASE a number base that is not between 2 and 36.
+- FloatStackUnderflow: when an operation attempts to pop an element from an empty float stack (only with the "float" feature).
+- FloatStackOverflow: when an operation attempts to push an element onto a float stack that is at its maximum memory capacity (only with the "float" feature).
+- InvalidLoop: when a loop word (LOOP, +LOOP, I, J, LEAVE, UNTIL, WHILE, REPEAT, AGAIN) is used without its loop being executed.
+- IncludeCycle: tuple that contains the path of a source file that is included while it is being read, directly or through other files.
+- InFile: tuple that contains the path of an included source file, the number of the line and the error found while reading it.
 - WrongInput: when the format in which the program is executed is incorrect.
 - Generic: generic tuple that contains a String used for other possible errors detected.
 */
@@ -18,6 +30,18 @@ pub enum ForthError {
     InvalidWord,
     DivisionByZero,
     UnknownWord,
+    InvalidLoop,
+    ReturnStackUnderflow,
+    ReturnStackOverflow,
+    CallDepthExceeded,
+    InvalidAddress,
+    MemoryOverflow,
+    Overflow,
+    InvalidBase,
+    FloatStackUnderflow,
+    FloatStackOverflow,
+    IncludeCycle(String),
+    InFile(String, usize, Box<ForthError>),
     WrongInput,
     Generic(String),
 }
@@ -35,9 +59,21 @@ impl Display for ForthError {
             ForthError::InvalidWord => write!(f, "invalid-word"),
             ForthError::DivisionByZero => write!(f, "division-by-zero"),
             ForthError::UnknownWord => write!(f, "?"),
+            ForthError::InvalidLoop => write!(f, "invalid-loop"),
+            ForthError::ReturnStackUnderflow => write!(f, "return-stack-underflow"),
+            ForthError::ReturnStackOverflow => write!(f, "return-stack-overflow"),
+            ForthError::CallDepthExceeded => write!(f, "call-depth-exceeded"),
+            ForthError::InvalidAddress => write!(f, "invalid-address"),
+            ForthError::MemoryOverflow => write!(f, "memory-overflow"),
+            ForthError::Overflow => write!(f, "overflow"),
+            ForthError::InvalidBase => write!(f, "invalid-base"),
+            ForthError::FloatStackUnderflow => write!(f, "float-stack-underflow"),
+            ForthError::FloatStackOverflow => write!(f, "float-stack-overflow"),
+            ForthError::IncludeCycle(path) => write!(f, "include-cycle: {path}"),
+            ForthError::InFile(path, line, error) => write!(f, "{path}:{line}: {error}"),
             ForthError::WrongInput => write!(
                 f,
-                "wrong-input. Try executing with format: cargo run -- path/to/main.fth stack-size=[size_of_stack]"
+                "wrong-input. Try executing with format: cargo run -- [path/to/main.fth] [stack-size=size_of_stack] [--compat-output] (without a file, an interactive session is started)"
             ),
             ForthError::Generic(value) => write!(f, "[ERROR]: {value}"),
         }
@@ -70,11 +106,17 @@ mod tests {
 
         for op in ops {
             let mut stack = Stack::new(10); //inicializacion sin valores a pushear
-            execute_arithmetic_op(&op, &mut stack);
+            assert_eq!(
+                execute_arithmetic_op(&op, &mut stack),
+                Err(ForthError::StackUnderflow)
+            );
             assert!(stack.data.is_empty());
 
             let mut stack = init_stack(&[1]);
-            execute_arithmetic_op(&op, &mut stack);
+            assert_eq!(
+                execute_arithmetic_op(&op, &mut stack),
+                Err(ForthError::StackUnderflow)
+            );
             assert!(stack.data.is_empty());
         }
     }
@@ -92,24 +134,36 @@ mod tests {
 
         for op in ops {
             let mut test_stack = Stack::new(10); //inicializacion sin valores a pushear
-            execute_stack_op(&op, &mut test_stack);
+            assert_eq!(
+                execute_stack_op(&op, &mut test_stack),
+                Err(ForthError::StackUnderflow)
+            );
             assert!(test_stack.data.is_empty());
         }
 
         //hay algunas operaciones de stack que con un elemento también generan stack-underflow, como swap y over
         let mut test_stack = init_stack(&[1]);
-        execute_stack_op(&StackOperation::Swap, &mut test_stack);
+        assert_eq!(
+            execute_stack_op(&StackOperation::Swap, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
         assert!(test_stack.data.is_empty());
 
         let mut test_stack = init_stack(&[1]);
-        execute_stack_op(&StackOperation::Over, &mut test_stack);
+        assert_eq!(
+            execute_stack_op(&StackOperation::Over, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
         assert!(test_stack.data.is_empty());
     }
 
     #[test]
     fn test_division_by_zero() {
         let mut test_stack = init_stack(&[4, 0]);
-        execute_arithmetic_op(&ArithmeticOperation::Divide, &mut test_stack);
+        assert_eq!(
+            execute_arithmetic_op(&ArithmeticOperation::Divide, &mut test_stack),
+            Err(ForthError::DivisionByZero)
+        );
         assert!(test_stack.data.is_empty());
     }
 
@@ -136,7 +190,10 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut stack = Stack::new(100);
 
-        read_tokens(&tokenize(": 1 2 ;"), &mut stack, &mut dict);
+        assert_eq!(
+            read_tokens(&tokenize(": 1 2 ;"), &mut stack, &mut dict),
+            Err(ForthError::InvalidWord)
+        );
         assert!(stack.data.is_empty());
     }
 
@@ -145,7 +202,35 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut stack = Stack::new(100);
 
-        read_tokens(&tokenize("foo"), &mut stack, &mut dict);
+        assert_eq!(
+            read_tokens(&tokenize("foo"), &mut stack, &mut dict),
+            Err(ForthError::UnknownWord)
+        );
+        assert!(stack.data.is_empty());
+    }
+
+    #[test]
+    fn test_error_aborts_line() {
+        let mut dict = WordsDictionary::new();
+        let mut stack = Stack::new(100);
+
+        assert_eq!(
+            read_tokens(&tokenize("1 2 + drop drop 3 4"), &mut stack, &mut dict),
+            Err(ForthError::StackUnderflow)
+        );
+        assert!(stack.data.is_empty());
+    }
+
+    #[test]
+    fn test_error_aborts_word() {
+        let mut dict = WordsDictionary::new();
+        let mut stack = Stack::new(100);
+
+        read_tokens(&tokenize(": f 1 0 / 5 ;"), &mut stack, &mut dict).unwrap();
+        assert_eq!(
+            read_tokens(&tokenize("f 6"), &mut stack, &mut dict),
+            Err(ForthError::DivisionByZero)
+        );
         assert!(stack.data.is_empty());
     }
 }
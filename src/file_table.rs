@@ -0,0 +1,184 @@
+use crate::cell::Cell;
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, ErrorKind, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// Directory where the files opened by the program are searched when the program starts (the current one).
+pub const DEFAULT_FILE_ROOT: &str = ".";
+
+/// The ior code left by a file operation that succeeds.
+pub const IOR_SUCCESS: Cell = 0;
+/// The ior code (file I/O exception) left when a file can not be used, including an invalid file id or access method.
+pub const IOR_FILE_IO: Cell = -37;
+/// The ior code (non-existent file) left when a file is not found, or its name leaves the sandbox root.
+pub const IOR_NON_EXISTENT_FILE: Cell = -38;
+
+/// The access method that opens a file only to read it ('R/O').
+pub const READ_ONLY: Cell = 0;
+/// The access method that opens a file only to write it ('W/O').
+pub const WRITE_ONLY: Cell = 1;
+/// The access method that opens a file to read and write it ('R/W').
+pub const READ_WRITE: Cell = 2;
+
+/// This struct keeps the files opened by the program, which are referred by their file id (their position in files plus one, so 0 is never a valid one).
+/// A closed file leaves its position empty, so it can be taken by the next file opened.
+/// The names of the files are relative to the root directory (the sandbox root): absolute names and names with '..' are rejected, so no file outside of it can be used.
+#[derive(Debug)]
+pub struct FileTable {
+    root: PathBuf,
+    files: Vec<Option<BufReader<File>>>,
+}
+
+impl FileTable {
+    /// Function used to build the structure, without files, with the root directory received by parameter.
+    pub fn new(root: &str) -> Self {
+        FileTable {
+            root: PathBuf::from(root),
+            files: Vec::new(),
+        }
+    }
+
+    /// Function used to open the file whose name is received by parameter with an access method (or create it, truncating it if it exists, if create is true).
+    /// Returns the file id of the opened file, or the error found (of kind NotFound if the name leaves the root directory, or InvalidInput if the access method is not valid).
+    pub fn open(&mut self, name: &str, access: Cell, create: bool) -> io::Result<Cell> {
+        let path = self.resolve(name)?;
+        let mut options = OpenOptions::new();
+        match access {
+            READ_ONLY => options.read(true),
+            WRITE_ONLY => options.write(true),
+            READ_WRITE => options.read(true).write(true),
+            _ => return Err(ErrorKind::InvalidInput.into()),
+        };
+        if create {
+            options.write(true).create(true).truncate(true);
+        }
+        let file = BufReader::new(options.open(path)?);
+        let position = match self.files.iter().position(Option::is_none) {
+            Some(position) => {
+                self.files[position] = Some(file);
+                position
+            }
+            None => {
+                self.files.push(Some(file));
+                self.files.len() - 1
+            }
+        };
+        Ok(position as Cell + 1)
+    }
+
+    /// Function used to close the file of the file id received by parameter. Returns an error of kind InvalidInput if no file is open with that id.
+    pub fn close(&mut self, id: Cell) -> io::Result<()> {
+        self.file(id)?;
+        self.files[id as usize - 1] = None;
+        Ok(())
+    }
+
+    /// Function used to read a line of up to max_length chars from the file of the file id received by parameter. The newline that ends it (if any) is not kept, nor are the carriage returns.
+    /// If the line is longer, the rest of it is read by the next call. Returns None if the end of the file was reached, or the error found.
+    pub fn read_line(&mut self, id: Cell, max_length: usize) -> io::Result<Option<Vec<u8>>> {
+        let file = self.file(id)?;
+        if file.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        let mut line = Vec::new();
+        while line.len() < max_length {
+            let Some(&byte) = file.fill_buf()?.first() else {
+                break;
+            };
+            file.consume(1);
+            match byte {
+                b'\n' => break,
+                b'\r' => {}
+                _ => line.push(byte),
+            }
+        }
+        Ok(Some(line))
+    }
+
+    /// Function used to write the bytes received by parameter to the file of the file id received by parameter, after the last byte read or written (discarding the bytes read ahead). Returns the error found, if any.
+    pub fn write(&mut self, id: Cell, bytes: &[u8]) -> io::Result<()> {
+        let file = self.file(id)?;
+        let position = file.stream_position()?;
+        file.seek(SeekFrom::Start(position))?;
+        file.get_mut().write_all(bytes)
+    }
+
+    /// Function that returns the open file of the file id received by parameter, or an error of kind InvalidInput if there is none.
+    fn file(&mut self, id: Cell) -> io::Result<&mut BufReader<File>> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| id.checked_sub(1))
+            .and_then(|position| self.files.get_mut(position))
+            .and_then(Option::as_mut)
+            .ok_or_else(|| ErrorKind::InvalidInput.into())
+    }
+
+    /// Function that returns the root directory, which the names of the files are relative to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Function that joins the name received by parameter to the root directory. Returns an error of kind NotFound if the name is absolute or has '..'.
+    fn resolve(&self, name: &str) -> io::Result<PathBuf> {
+        let path = Path::new(name);
+        let inside_root = path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if name.is_empty() || !inside_root {
+            return Err(ErrorKind::NotFound.into());
+        }
+        Ok(self.root.join(path))
+    }
+}
+
+/// Function that converts the error received by parameter to the ior code it is reported with.
+pub fn ior_code(error: &io::Error) -> Cell {
+    match error.kind() {
+        ErrorKind::NotFound => IOR_NON_EXISTENT_FILE,
+        _ => IOR_FILE_IO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileTable, IOR_FILE_IO, IOR_NON_EXISTENT_FILE, READ_ONLY, READ_WRITE, ior_code};
+    use std::fs;
+
+    #[test]
+    fn test_write_and_read_lines() {
+        let root = std::env::temp_dir().join("forth_file_table_test");
+        fs::create_dir_all(&root).unwrap();
+        let mut files = FileTable::new(root.to_str().unwrap());
+
+        let id = files.open("lines.txt", READ_WRITE, true).unwrap();
+        assert_eq!(id, 1);
+        files.write(id, b"hello\r\nworld\n").unwrap();
+        files.close(id).unwrap();
+
+        let id = files.open("./lines.txt", READ_ONLY, false).unwrap();
+        assert_eq!(id, 1);
+        assert_eq!(files.read_line(id, 10).unwrap(), Some(b"hello".to_vec()));
+        assert_eq!(files.read_line(id, 3).unwrap(), Some(b"wor".to_vec()));
+        assert_eq!(files.read_line(id, 10).unwrap(), Some(b"ld".to_vec()));
+        assert_eq!(files.read_line(id, 10).unwrap(), None);
+        files.close(id).unwrap();
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        let mut files = FileTable::new(".");
+        for name in ["../lines.txt", "/etc/passwd", "", "missing_forth_file.txt"] {
+            let error = files.open(name, READ_ONLY, false).unwrap_err();
+            assert_eq!(ior_code(&error), IOR_NON_EXISTENT_FILE);
+        }
+        let error = files.open("Cargo.toml", 3, false).unwrap_err();
+        assert_eq!(ior_code(&error), IOR_FILE_IO);
+        for id in [-1, 0, 1] {
+            let error = files.close(id).unwrap_err();
+            assert_eq!(ior_code(&error), IOR_FILE_IO);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::errors::ForthError;
+
+/// Amount of bytes of each value of the FloatStack.
+const FLOAT_BYTES: usize = 8;
+
+/// This struct is the float stack of the program (only with the "float" feature), where the floating-point values are kept apart from the cells of the Stack.
+/// It contains data (as pub so it can be accessed by other modules) of f64 values. The max_elements field is used to bound the structure by the size received in the program input.
+#[derive(Debug)]
+pub struct FloatStack {
+    pub data: Vec<f64>,
+    max_elements: usize,
+}
+
+impl FloatStack {
+    /// Function used to build the structure. Receives a size in bytes that sets the max_elements of the FloatStack (8 bytes for each value).
+    pub fn new(size: usize) -> Self {
+        FloatStack {
+            data: Vec::new(),
+            max_elements: size / FLOAT_BYTES,
+        }
+    }
+
+    /// Function to add a f64 value to the top of the FloatStack. Returns Ok if possible or float-stack-overflow error if it exceeds the structure max_elements.
+    pub fn push(&mut self, value: f64) -> Result<(), ForthError> {
+        if self.data.len() >= self.max_elements {
+            Err(ForthError::FloatStackOverflow)
+        } else {
+            self.data.push(value);
+            Ok(())
+        }
+    }
+
+    /// Function to get the top f64 value of the FloatStack. Returns the value if possible or float-stack-underflow if the element does not exists.
+    pub fn pop(&mut self) -> Result<f64, ForthError> {
+        self.data.pop().ok_or(ForthError::FloatStackUnderflow)
+    }
+
+    /// Function to get a copy of the top f64 value of the FloatStack without removing it. Returns float-stack-underflow if the element does not exists.
+    pub fn peek(&self) -> Result<f64, ForthError> {
+        self.data
+            .last()
+            .copied()
+            .ok_or(ForthError::FloatStackUnderflow)
+    }
+}
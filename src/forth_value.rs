@@ -1,4 +1,4 @@
-use crate::{operations::forth_operation::ForthOperation, words::word::ForthWord};
+use crate::{cell::Cell, operations::forth_operation::ForthOperation, words::word::ForthWord};
 
 /// Enum that represents the values that can be interpreted by the program.
 ///
@@ -6,12 +6,19 @@ use crate::{operations::forth_operation::ForthOperation, words::word::ForthWord}
 ///
 /// - Operation: tuple that contains a ForthOperation.
 /// - Word: tuple that contains a ForthOperation.
-/// - Number: tuple that contains an i16 number.
+/// - Number: tuple that contains a Cell number.
+/// - DoubleNumber: tuple that contains the low and high cells of a double-cell number.
+/// - String: tuple that contains the address (in the data memory) and the length of a string ('S"').
+/// - FloatNumber: tuple that contains a f64 number, for the float stack (only with the "float" feature).
 ///
 
 #[derive(Debug)]
 pub enum ForthValue {
     Operation(ForthOperation),
     Word(ForthWord),
-    Number(i16),
+    Number(Cell),
+    DoubleNumber(Cell, Cell),
+    String(Cell, Cell),
+    #[cfg(feature = "float")]
+    FloatNumber(f64),
 }
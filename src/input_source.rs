@@ -0,0 +1,88 @@
+use crate::errors::ForthError;
+use std::{
+    fmt::Debug,
+    io::{self, BufRead},
+};
+
+/// Trait implemented by the sources of the chars read by the program ('KEY', 'KEY?', 'ACCEPT' and 'EXPECT').
+/// The chars are read one by one as their codes (bytes), until the end of the input is reached.
+pub trait InputSource: Debug {
+    /// Function used to read the next char code, or None if the end of the input was reached. Returns a generic error if it could not be read.
+    fn read_char(&mut self) -> Result<Option<u8>, ForthError>;
+
+    /// Function that returns whether there is a char left to read (false once the end of the input is reached). Returns a generic error if the input could not be read.
+    /// It may wait for the next char: a source that can not know it without reading (as the standard input) blocks until a char arrives or the input is closed.
+    fn has_char(&mut self) -> Result<bool, ForthError>;
+}
+
+/// This struct reads the chars from the standard input. Checking whether there is a char left ('KEY?') is blocking: there is no portable way to peek at the standard input
+/// without waiting, so it waits until one is typed (or the input is closed) instead of returning false while the user is not typing.
+#[derive(Debug, Default)]
+pub struct StdinInput;
+
+impl InputSource for StdinInput {
+    fn read_char(&mut self) -> Result<Option<u8>, ForthError> {
+        let mut stdin = io::stdin().lock();
+        let code = stdin.fill_buf().map_err(input_error)?.first().copied();
+        if code.is_some() {
+            stdin.consume(1);
+        }
+        Ok(code)
+    }
+
+    fn has_char(&mut self) -> Result<bool, ForthError> {
+        let mut stdin = io::stdin().lock();
+        Ok(!stdin.fill_buf().map_err(input_error)?.is_empty())
+    }
+}
+
+/// Function that builds the error returned when the standard input could not be read.
+fn input_error(_: io::Error) -> ForthError {
+    ForthError::Generic("Impossible to read input".to_string())
+}
+
+/// This struct reads the chars from a text kept in memory (for example, in tests), from its position to its end.
+#[derive(Debug, Default)]
+pub struct BufferInput {
+    text: Vec<u8>,
+    position: usize,
+}
+
+impl BufferInput {
+    /// Function used to build the structure with the text received by parameter, which is read from its first char.
+    pub fn new(text: &str) -> Self {
+        BufferInput {
+            text: text.as_bytes().to_vec(),
+            position: 0,
+        }
+    }
+}
+
+impl InputSource for BufferInput {
+    fn read_char(&mut self) -> Result<Option<u8>, ForthError> {
+        let code = self.text.get(self.position).copied();
+        if code.is_some() {
+            self.position += 1;
+        }
+        Ok(code)
+    }
+
+    fn has_char(&mut self) -> Result<bool, ForthError> {
+        Ok(self.position < self.text.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferInput, InputSource};
+
+    #[test]
+    fn test_buffer_input() {
+        let mut input = BufferInput::new("ab");
+        assert!(input.has_char().unwrap());
+        assert_eq!(input.read_char().unwrap(), Some(b'a'));
+        assert_eq!(input.read_char().unwrap(), Some(b'b'));
+        assert!(!input.has_char().unwrap());
+        assert_eq!(input.read_char().unwrap(), None);
+    }
+}
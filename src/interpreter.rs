@@ -0,0 +1,632 @@
+#[cfg(feature = "float")]
+use crate::float_stack::FloatStack;
+use crate::{
+    cell::{CellConfig, CellWidth, DivisionMode, OverflowPolicy},
+    context::ForthContext,
+    data_memory::DataMemory,
+    errors::ForthError,
+    file_table::{DEFAULT_FILE_ROOT, FileTable},
+    input_source::{InputSource, StdinInput},
+    operations::output::OutputMode,
+    output_sink::{OutputSink, StdoutOutput},
+    return_stack::ReturnStack,
+    stack::Stack,
+    tokens::{ReadingState, read_tokens_with_state, tokenize_with_state},
+    utils::{
+        get_copy_cell_width, get_copy_division_mode, get_copy_output_mode,
+        get_copy_overflow_policy, read_file,
+    },
+    words::dictionary::WordsDictionary,
+};
+use std::path::Path;
+
+pub const DEFAULT_STACK_SIZE: usize = 1024 * 128; //128KB
+pub const DEFAULT_MEMORY_SIZE: usize = 1024 * 32; //32KB
+pub const DEFAULT_RETURN_STACK_SIZE: usize = 1024 * 8; //8KB
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 256;
+pub const DEFAULT_FLOAT_STACK_SIZE: usize = 1024 * 8; //8KB
+
+/// This struct contains the configuration used to build an Interpreter.
+/// It contains the size of the stack in bytes (stack_size), which sets how many cells it can hold (by the bytes of each one),
+/// the size of the data memory in bytes (memory_size), which sets how many cells can be allotted,
+/// the size of the return stack in bytes (return_stack_size), which sets how many values, loops and word calls can be nested,
+/// the max amount of words executing at the same time (max_call_depth, which is bounded by MAX_CALL_DEPTH_LIMIT so a deep recursion can not overflow the native stack), if a word can call itself by its name (allow_self_calls) instead of only with 'RECURSE',
+/// how the printed text is formatted (output_mode), the width of the cells (cell_width), what happens when a value does not fit in them (overflow_policy)
+/// how the quotient of a division is rounded (division_mode), the directory the files opened by the program are relative to, which they can not leave (file_root) and, with the "float" feature, the size of the float stack in bytes (float_stack_size).
+#[derive(Debug)]
+pub struct InterpreterConfig {
+    pub stack_size: usize,
+    pub memory_size: usize,
+    pub return_stack_size: usize,
+    /// Bounded by MAX_CALL_DEPTH_LIMIT: a greater value builds a ReturnStack with that limit instead.
+    pub max_call_depth: usize,
+    pub allow_self_calls: bool,
+    pub output_mode: OutputMode,
+    pub cell_width: CellWidth,
+    pub overflow_policy: OverflowPolicy,
+    pub division_mode: DivisionMode,
+    pub file_root: String,
+    #[cfg(feature = "float")]
+    pub float_stack_size: usize,
+}
+
+impl Default for InterpreterConfig {
+    fn default() -> Self {
+        InterpreterConfig {
+            stack_size: DEFAULT_STACK_SIZE,
+            memory_size: DEFAULT_MEMORY_SIZE,
+            return_stack_size: DEFAULT_RETURN_STACK_SIZE,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            allow_self_calls: false,
+            output_mode: OutputMode::Standard,
+            cell_width: CellWidth::Bits16,
+            overflow_policy: OverflowPolicy::Wrapping,
+            division_mode: DivisionMode::Symmetric,
+            file_root: DEFAULT_FILE_ROOT.to_string(),
+            #[cfg(feature = "float")]
+            float_stack_size: DEFAULT_FLOAT_STACK_SIZE,
+        }
+    }
+}
+
+/// This struct is the entry point of the library. It owns the Stack, the ForthContext (DataMemory, ReturnStack and OutputSink), the WordsDictionary and the ReadingState, so the code evaluated
+/// by each call can use the values and words left by the previous ones (or continue a definition left open), without the caller knowing how they are wired.
+/// The printed text goes to the standard output, unless another OutputSink is set, and the chars read by the program come from the standard input, unless another InputSource is set.
+#[derive(Debug)]
+pub struct Interpreter {
+    stack: Stack,
+    context: ForthContext,
+    dictionary: WordsDictionary,
+    reading_state: ReadingState,
+    config: InterpreterConfig,
+}
+
+/// Function that builds the Stack of the configuration received by parameter, with its size and its cells.
+fn build_stack(config: &InterpreterConfig) -> Stack {
+    let cell = CellConfig {
+        width: get_copy_cell_width(&config.cell_width),
+        overflow: get_copy_overflow_policy(&config.overflow_policy),
+        division: get_copy_division_mode(&config.division_mode),
+    };
+    Stack::with_cells(config.stack_size, cell)
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new(InterpreterConfig::default())
+    }
+}
+
+impl Interpreter {
+    /// Function used to build the structure with the configuration received by parameter.
+    pub fn new(config: InterpreterConfig) -> Self {
+        let stack = build_stack(&config);
+        Interpreter {
+            context: ForthContext {
+                memory: DataMemory::with_cells(config.memory_size, stack.cell()),
+                return_stack: ReturnStack::with_cells(
+                    config.return_stack_size,
+                    config.max_call_depth,
+                    stack.cell(),
+                ),
+                output: Box::new(StdoutOutput),
+                input: Box::new(StdinInput),
+                files: FileTable::new(&config.file_root),
+                output_mode: get_copy_output_mode(&config.output_mode),
+                #[cfg(feature = "float")]
+                float_stack: FloatStack::new(config.float_stack_size),
+            },
+            stack,
+            dictionary: WordsDictionary::new(),
+            reading_state: ReadingState::with_self_calls(config.allow_self_calls),
+            config,
+        }
+    }
+
+    /// Function used to evaluate Forth code received as &str. Each line is tokenized and executed in order.
+    /// Word definitions, control structures and dot-quote strings left open are continued by the next line (or the next call).
+    /// The evaluation stops on the first error, which is returned so the caller can decide how to report it.
+    pub fn eval(&mut self, code: &str) -> Result<(), ForthError> {
+        for line in code.lines() {
+            let tokens = tokenize_with_state(line, &mut self.reading_state);
+            read_tokens_with_state(
+                &tokens,
+                &mut self.stack,
+                &mut self.context,
+                &mut self.dictionary,
+                &mut self.reading_state,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Function used to evaluate Forth code received as &str (as eval does), read from the source file of the path received by parameter,
+    /// so the relative paths it includes are searched next to that file and including it again is detected as a cycle.
+    /// The code is only a part of the file, so the file is not marked as loaded for 'REQUIRE' (see eval_file and eval_file_reporting).
+    pub fn eval_from(&mut self, code: &str, path: &str) -> Result<(), ForthError> {
+        self.reading_state.push_source(Path::new(path));
+        let result = self.eval(code);
+        self.reading_state.pop_source(false);
+        result
+    }
+
+    /// Function used when there is no more code to evaluate. Returns an error if a word definition, control structure or dot-quote string was left open,
+    /// discarding it.
+    pub fn finish(&mut self) -> Result<(), ForthError> {
+        self.reading_state.finish()
+    }
+
+    /// Function used to evaluate the Forth file found in the path received by parameter. The whole file must be complete (see finish),
+    /// and it is marked as loaded for 'REQUIRE' only if it was read without errors.
+    /// Returns a generic error if the file can not be read, or the first error found while evaluating it.
+    pub fn eval_file(&mut self, path: &str) -> Result<(), ForthError> {
+        let lines = read_file(path)
+            .map_err(|_| ForthError::Generic(format!("Impossible to read {path}")))?;
+        self.reading_state.push_source(Path::new(path));
+        let result = lines
+            .iter()
+            .try_for_each(|line| self.eval(line))
+            .and_then(|_| self.finish());
+        self.reading_state.pop_source(result.is_ok());
+        result
+    }
+
+    /// Function used to evaluate every line of the Forth file found in the path received by parameter (as eval_file does), passing each error found to the function report
+    /// and going on with the next line. The file is marked as loaded for 'REQUIRE' only if no error was found.
+    /// Returns a generic error if the file can not be read.
+    pub fn eval_file_reporting(
+        &mut self,
+        path: &str,
+        mut report: impl FnMut(ForthError),
+    ) -> Result<(), ForthError> {
+        let lines = read_file(path)
+            .map_err(|_| ForthError::Generic(format!("Impossible to read {path}")))?;
+        self.reading_state.push_source(Path::new(path));
+        let mut loaded = true;
+        for line in lines {
+            if let Err(error) = self.eval(&line) {
+                loaded = false;
+                report(error);
+            }
+        }
+        if let Err(error) = self.finish() {
+            loaded = false;
+            report(error);
+        }
+        self.reading_state.pop_source(loaded);
+        Ok(())
+    }
+
+    /// Function used to get a reference to the Stack, so its values can be read.
+    pub fn stack(&self) -> &Stack {
+        &self.stack
+    }
+
+    /// Function used to get a reference to the FloatStack (only with the "float" feature), so its values can be read.
+    #[cfg(feature = "float")]
+    pub fn float_stack(&self) -> &FloatStack {
+        &self.context.float_stack
+    }
+
+    /// Function used to get a reference to the DataMemory, so its cells can be read.
+    pub fn memory(&self) -> &DataMemory {
+        &self.context.memory
+    }
+
+    /// Function used to get a reference to the OutputSink, so the text kept in memory (if any) can be read.
+    pub fn output(&self) -> &dyn OutputSink {
+        self.context.output.as_ref()
+    }
+
+    /// Function used to set the OutputSink received by parameter as the destination of the printed text, replacing the current one.
+    pub fn set_output(&mut self, output: Box<dyn OutputSink>) {
+        self.context.output = output;
+    }
+
+    /// Function used to write the text received by parameter to the OutputSink, after the text printed so far (for example, the errors reported by the caller).
+    pub fn write_output(&mut self, text: &str) -> Result<(), ForthError> {
+        self.context.output.write_text(text)
+    }
+
+    /// Function used to set the InputSource received by parameter as the source of the chars read by the program, replacing the current one.
+    pub fn set_input(&mut self, input: Box<dyn InputSource>) {
+        self.context.input = input;
+    }
+
+    /// Function used to get a reference to the WordsDictionary, so the defined words can be consulted.
+    pub fn dictionary(&self) -> &WordsDictionary {
+        &self.dictionary
+    }
+
+    /// Function used to discard every value of the Stack, the DataMemory and the ReturnStack, every defined word, every open file, every loaded source file and anything left open, keeping the configuration, the OutputSink and the InputSource.
+    pub fn reset(&mut self) {
+        self.stack = build_stack(&self.config);
+        self.context.memory = DataMemory::with_cells(self.config.memory_size, self.stack.cell());
+        self.context.return_stack = ReturnStack::with_cells(
+            self.config.return_stack_size,
+            self.config.max_call_depth,
+            self.stack.cell(),
+        );
+        #[cfg(feature = "float")]
+        {
+            self.context.float_stack = FloatStack::new(self.config.float_stack_size);
+        }
+        self.context.files = FileTable::new(&self.config.file_root);
+        self.dictionary = WordsDictionary::new();
+        self.reading_state = ReadingState::with_self_calls(self.config.allow_self_calls);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interpreter, InterpreterConfig};
+    use crate::{
+        cell::{CellWidth, DivisionMode, OverflowPolicy},
+        errors::ForthError,
+        output_sink::BufferOutput,
+    };
+
+    #[test]
+    fn test_eval_keeps_state() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": double 2 * ;").unwrap();
+        forth.eval("3 double").unwrap();
+        forth.eval("double").unwrap();
+
+        assert_eq!(forth.stack().data, &[12]);
+    }
+
+    #[test]
+    fn test_eval_multiple_lines() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": double 2 * ;\n3 double\n1").unwrap();
+
+        assert_eq!(forth.stack().data, &[6, 1]);
+    }
+
+    #[test]
+    fn test_multi_line_definition() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": classify").unwrap();
+        forth.eval("  dup 0 = if").unwrap();
+        forth.eval("    drop 10").unwrap();
+        forth.eval("  else").unwrap();
+        forth.eval("    drop 20").unwrap();
+        forth.eval("  then ;").unwrap();
+        forth.eval("0 classify 1 classify").unwrap();
+
+        assert_eq!(forth.stack().data, &[10, 20]);
+    }
+
+    #[test]
+    fn test_multi_line_loop_and_if() {
+        let mut forth = Interpreter::default();
+
+        forth.eval("3 0 do\n  i\nloop\n-1 if\n  7\nthen").unwrap();
+
+        assert_eq!(forth.stack().data, &[0, 1, 2, 7]);
+    }
+
+    #[test]
+    fn test_cell_width_and_overflow_policy() {
+        let mut forth = Interpreter::new(InterpreterConfig {
+            stack_size: 16,
+            cell_width: CellWidth::Bits32,
+            overflow_policy: OverflowPolicy::Error,
+            ..Default::default()
+        });
+
+        forth.eval("70000 2 *").unwrap();
+        assert_eq!(forth.stack().data, &[140000]);
+        assert_eq!(forth.eval("3000000000"), Err(ForthError::Overflow));
+        assert_eq!(forth.eval("2147483647 1 +"), Err(ForthError::Overflow));
+        forth.eval("1 2").unwrap();
+        assert_eq!(forth.eval("3 4"), Err(ForthError::StackOverflow));
+
+        let mut forth = Interpreter::default();
+        forth.eval("70000 32767 1 +").unwrap();
+        assert_eq!(forth.stack().data, &[4464, -32768]);
+    }
+
+    #[test]
+    fn test_division_mode() {
+        let mut forth = Interpreter::default();
+        forth.eval("-7 2 /mod -7 2 mod").unwrap();
+        assert_eq!(forth.stack().data, &[-1, -3, -1]);
+
+        let mut forth = Interpreter::new(InterpreterConfig {
+            division_mode: DivisionMode::Floored,
+            ..Default::default()
+        });
+        forth.eval("-7 2 /mod -7 2 /").unwrap();
+        assert_eq!(forth.stack().data, &[1, -4, -4]);
+        assert_eq!(forth.eval("1 0 mod"), Err(ForthError::DivisionByZero));
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn test_float_stack() {
+        let mut forth = Interpreter::new(InterpreterConfig {
+            float_stack_size: 16,
+            ..Default::default()
+        });
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth
+            .eval(": half 2e0 f/ ; 3 s>f half fdup f. 1.5e0 f< 2.75e0 f>s")
+            .unwrap();
+        assert_eq!(forth.stack().data, &[0, 2]);
+        assert!(forth.float_stack().data.is_empty());
+        assert_eq!(forth.output().contents(), Some("1.5 "));
+        assert_eq!(forth.eval("1e 2e 3e"), Err(ForthError::FloatStackOverflow));
+        assert_eq!(
+            forth.eval("fdrop fdrop fdrop"),
+            Err(ForthError::FloatStackUnderflow)
+        );
+
+        forth.reset();
+        assert!(forth.float_stack().data.is_empty());
+    }
+
+    #[test]
+    fn test_unbalanced_control_structures() {
+        let mut forth = Interpreter::default();
+
+        assert_eq!(forth.eval(": f 1 if 2 ;"), Err(ForthError::InvalidWord));
+        assert_eq!(forth.eval(": g 3 0 do i ;"), Err(ForthError::InvalidLoop));
+        assert_eq!(forth.eval(": h then ;"), Err(ForthError::InvalidWord));
+        assert_eq!(forth.eval("f"), Err(ForthError::UnknownWord));
+        assert_eq!(forth.eval("1 if 2 loop"), Err(ForthError::InvalidLoop));
+        assert_eq!(forth.eval("then"), Err(ForthError::InvalidWord));
+        assert_eq!(forth.eval("else"), Err(ForthError::InvalidWord));
+        forth.eval("5 1 if 6 then").unwrap();
+
+        assert_eq!(forth.stack().data, &[1, 5, 6]);
+    }
+
+    #[test]
+    fn test_nested_words_are_called() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": one 1 ;").unwrap();
+        for n in 1..20 {
+            forth.eval(&format!(": w{n} one one ;")).unwrap();
+            forth.eval(&format!(": one w{n} ;")).unwrap();
+        }
+        forth.eval("w3").unwrap();
+
+        assert_eq!(forth.stack().data, &[1; 8]);
+    }
+
+    #[test]
+    fn test_multi_line_dot_quote() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": greet .\" hello").unwrap();
+        forth.eval("world\" 1 ;").unwrap();
+        forth.eval("greet").unwrap();
+
+        assert_eq!(forth.stack().data, &[1]);
+    }
+
+    #[test]
+    fn test_finish_with_open_definition() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": foo 1").unwrap();
+        assert_eq!(forth.finish(), Err(ForthError::InvalidWord));
+        assert_eq!(forth.eval("foo"), Err(ForthError::UnknownWord));
+    }
+
+    #[test]
+    fn test_error_discards_open_definition() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": foo 1").unwrap();
+        assert_eq!(forth.eval(": bar"), Err(ForthError::InvalidWord));
+        forth.eval("2").unwrap();
+
+        assert_eq!(forth.stack().data, &[2]);
+        assert_eq!(forth.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_eval_returns_error() {
+        let mut forth = Interpreter::default();
+
+        assert_eq!(forth.eval("1 +\n2"), Err(ForthError::StackUnderflow));
+        assert!(forth.stack().data.is_empty());
+    }
+
+    #[test]
+    fn test_eval_file_not_found() {
+        let mut forth = Interpreter::default();
+
+        assert!(matches!(
+            forth.eval_file("does/not/exist.fth"),
+            Err(ForthError::Generic(_))
+        ));
+    }
+
+    #[test]
+    fn test_stack_size_config() {
+        let mut forth = Interpreter::new(InterpreterConfig {
+            stack_size: 4,
+            ..Default::default()
+        });
+
+        assert_eq!(forth.eval("1 2 3"), Err(ForthError::StackOverflow));
+        assert_eq!(forth.stack().data, &[1, 2]);
+    }
+
+    #[test]
+    fn test_variables_and_constants() {
+        let mut forth = Interpreter::default();
+
+        forth.eval("variable counter 10 constant ten").unwrap();
+        forth.eval(": bump ten counter +! ;").unwrap();
+        forth.eval("bump bump counter @").unwrap();
+
+        assert_eq!(forth.stack().data, &[20]);
+        assert_eq!(forth.memory().cells, &[20]);
+    }
+
+    #[test]
+    fn test_memory_errors() {
+        let mut forth = Interpreter::new(InterpreterConfig {
+            memory_size: 4,
+            ..Default::default()
+        });
+
+        assert_eq!(forth.eval("5 @"), Err(ForthError::InvalidAddress));
+        assert_eq!(forth.eval("3 allot"), Err(ForthError::MemoryOverflow));
+        assert_eq!(forth.eval("constant"), Err(ForthError::InvalidWord));
+    }
+
+    #[test]
+    fn test_return_stack() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": under+ >r + r> ;").unwrap();
+        forth.eval(": indexes 3 0 do i >r r@ r> + loop ;").unwrap();
+        forth.eval("1 2 3 under+ 9 >r").unwrap();
+        forth.eval("indexes r>").unwrap();
+
+        assert_eq!(forth.stack().data, &[3, 3, 0, 2, 4, 9]);
+    }
+
+    #[test]
+    fn test_return_stack_errors() {
+        let mut forth = Interpreter::new(InterpreterConfig {
+            return_stack_size: 4,
+            ..Default::default()
+        });
+
+        forth.eval(": take r> ;").unwrap();
+        assert_eq!(
+            forth.eval("1 >r take"),
+            Err(ForthError::ReturnStackUnderflow)
+        );
+        assert_eq!(
+            forth.eval("1 >r 2 >r 3 >r"),
+            Err(ForthError::ReturnStackOverflow)
+        );
+        assert_eq!(forth.eval("r>"), Err(ForthError::ReturnStackUnderflow));
+        forth.eval(": nested 2 0 do 2 0 do loop loop ;").unwrap();
+        assert_eq!(forth.eval("nested"), Err(ForthError::ReturnStackOverflow));
+    }
+
+    #[test]
+    fn test_recurse() {
+        let mut forth = Interpreter::default();
+
+        forth
+            .eval(": fact dup 1 > if dup 1 - recurse * then ;")
+            .unwrap();
+        forth
+            .eval(": fib dup 1 > if dup 1 - recurse swap 2 - recurse + then ;")
+            .unwrap();
+        forth.eval("5 fact 10 fib").unwrap();
+
+        assert_eq!(forth.stack().data, &[120, 55]);
+    }
+
+    #[test]
+    fn test_self_calls_by_name() {
+        let mut forth = Interpreter::new(InterpreterConfig {
+            allow_self_calls: true,
+            ..Default::default()
+        });
+
+        forth
+            .eval(": countdown dup 0 > if dup 1 - countdown then ;")
+            .unwrap();
+        forth.eval("3 countdown").unwrap();
+
+        assert_eq!(forth.stack().data, &[3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_self_call_without_opt_in() {
+        let mut forth = Interpreter::default();
+
+        assert_eq!(
+            forth.eval(": countdown dup 0 > if dup 1 - countdown then ;"),
+            Err(ForthError::InvalidWord)
+        );
+        assert_eq!(forth.eval("countdown"), Err(ForthError::UnknownWord));
+
+        forth.eval(": countdown 100 ;").unwrap();
+        forth.eval(": countdown countdown 1 + ;").unwrap();
+        forth.eval("countdown").unwrap();
+        assert_eq!(forth.stack().data, &[101]);
+    }
+
+    #[test]
+    fn test_late_bound_mutual_recursion() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": ping dup if 1 - pong then ;").unwrap();
+        forth.eval(": pong ping ;").unwrap();
+        forth.eval("3 ping").unwrap();
+        assert_eq!(forth.stack().data, &[0]);
+    }
+
+    #[test]
+    fn test_max_call_depth() {
+        let mut forth = Interpreter::new(InterpreterConfig {
+            max_call_depth: 10,
+            ..Default::default()
+        });
+
+        forth.eval(": forever recurse ;").unwrap();
+        forth.eval(": deep dup if 1 - recurse then ;").unwrap();
+        assert_eq!(forth.eval("forever"), Err(ForthError::CallDepthExceeded));
+        assert_eq!(forth.eval("9 deep"), Ok(()));
+        assert_eq!(forth.eval("10 deep"), Err(ForthError::CallDepthExceeded));
+        assert_eq!(forth.eval("recurse"), Err(ForthError::InvalidWord));
+
+        let mut forth = Interpreter::default();
+        forth.eval(": deep dup if 1 - recurse then ;").unwrap();
+        assert_eq!(forth.eval("255 deep"), Ok(()));
+        assert_eq!(forth.eval("256 deep"), Err(ForthError::CallDepthExceeded));
+
+        let mut forth = Interpreter::new(InterpreterConfig {
+            max_call_depth: 10_000,
+            return_stack_size: 1024 * 1024,
+            ..Default::default()
+        });
+        forth.eval(": forever recurse ;").unwrap();
+        forth.eval(": deep dup if 1 - recurse then ;").unwrap();
+        assert_eq!(forth.eval("forever"), Err(ForthError::CallDepthExceeded));
+        assert_eq!(forth.eval("511 deep"), Ok(()));
+        assert_eq!(forth.eval("512 deep"), Err(ForthError::CallDepthExceeded));
+    }
+
+    #[test]
+    fn test_output_sink() {
+        let mut forth = Interpreter::default();
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth
+            .eval(": greet .\" hi\" ; 1 2 . greet 65 emit")
+            .unwrap();
+        forth.eval("cr .s").unwrap();
+
+        assert_eq!(forth.output().contents(), Some("2 hiA\n<1> 1 "));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut forth = Interpreter::default();
+
+        forth.eval(": foo 1 ; foo 2").unwrap();
+        forth.reset();
+
+        assert!(forth.stack().data.is_empty());
+        assert_eq!(forth.eval("foo"), Err(ForthError::UnknownWord));
+    }
+}
@@ -1,8 +1,23 @@
+pub mod bytecode;
+pub mod cell;
+pub mod context;
+pub mod data_memory;
 pub mod errors;
+pub mod file_table;
+#[cfg(feature = "float")]
+pub mod float_stack;
 pub mod forth_value;
+pub mod input_source;
+pub mod interpreter;
 pub mod operations;
 pub mod other_executions;
+pub mod output_sink;
+pub mod return_stack;
 pub mod stack;
 pub mod tokens;
 pub mod utils;
 pub mod words;
+
+pub use errors::{ForthError, print_error};
+pub use interpreter::{Interpreter, InterpreterConfig};
+pub use stack::Stack;
@@ -1,56 +1,84 @@
-pub mod errors;
-pub mod forth_value;
-pub mod operations;
-pub mod other_executions;
-pub mod stack;
-pub mod tokens;
-pub mod utils;
-pub mod words;
-
-pub use errors::{ForthError, print_error};
-pub use stack::Stack;
-use tokens::{read_tokens, tokenize};
-use utils::read_file;
-use words::dictionary::WordsDictionary;
-
-const DEFAULT_STACK_SIZE: usize = 1024 * 128; //128KB
-
-fn interpret_forth_file(filename: &str, stack: &mut Stack, dictionary: &mut WordsDictionary) {
-    match read_file(filename) {
-        Ok(lines) => {
-            for line in lines {
-                let tokens = tokenize(&line);
-                read_tokens(&tokens, stack, dictionary);
-            }
-
-            match stack.write_into_file() {
-                Ok(_) => println!("Stack ({:?}) written in stack.fth!", stack.data),
-                Err(_) => print_error(ForthError::Generic("Impossible to write stack".to_string())),
-            }
-        }
+use rust_the_forth::{
+    ForthError, Interpreter, InterpreterConfig, interpreter::DEFAULT_STACK_SIZE,
+    operations::output::OutputMode, print_error,
+};
+use std::io::{self, Write};
+
+const COMPAT_OUTPUT_FLAG: &str = "--compat-output";
+
+fn interpret_forth_file(filename: &str, interpreter: &mut Interpreter) {
+    match interpreter.eval_file_reporting(filename, print_error) {
+        Ok(_) => match interpreter.stack().write_into_file() {
+            Ok(_) => println!(
+                "Stack ({:?}) written in stack.fth!",
+                interpreter.stack().data
+            ),
+            Err(_) => print_error(ForthError::Generic("Impossible to write stack".to_string())),
+        },
         Err(_) => print_error(ForthError::Generic(
             "Impossible to read file.fth".to_string(),
         )),
     }
 }
 
+/// Function that starts an interactive session: every line read from stdin is evaluated against the same interpreter,
+/// printing " ok" or the error found after it. The session ends when stdin is closed.
+/// Stdin is not kept locked while a line is evaluated, so the words that read chars ('KEY', 'ACCEPT', ...) read the lines that follow it.
+fn run_repl(interpreter: &mut Interpreter) {
+    println!("Type Forth code and press enter. Use .S to see the stack, and Ctrl-D to exit.");
+    loop {
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let line = line.trim_end_matches(['\n', '\r']);
+        match interpreter.eval(line) {
+            Ok(_) => println!(" ok"),
+            Err(e) => print_error(e),
+        }
+        let _ = io::stdout().flush();
+    }
+    if let Err(e) = interpreter.finish() {
+        print_error(e);
+    }
+}
+
 fn main() {
     println!("----- Basic Forth-79 Interpreter -----");
-    let args: Vec<String> = std::env::args().collect();
+    let all_args: Vec<String> = std::env::args().collect();
+    let compat_output = all_args.iter().any(|arg| arg == COMPAT_OUTPUT_FLAG);
+    let args: Vec<&String> = all_args
+        .iter()
+        .filter(|arg| *arg != COMPAT_OUTPUT_FLAG)
+        .collect();
     if args.len() > 3 || (args.len() == 3 && !args[2].starts_with("stack-size=")) {
         print_error(ForthError::WrongInput);
         return;
     }
+    let filename = args.get(1).filter(|arg| !arg.starts_with("stack-size="));
 
-    let size_bytes = args
+    let stack_size = args
         .iter()
         .find(|arg| arg.starts_with("stack-size="))
         .and_then(|arg| arg.split('=').nth(1))
         .and_then(|s| s.trim().parse().ok())
         .unwrap_or(DEFAULT_STACK_SIZE);
 
-    let mut stack = Stack::new(size_bytes);
-    let mut words_dictionary = WordsDictionary::new();
+    let output_mode = if compat_output {
+        OutputMode::Compatibility
+    } else {
+        OutputMode::Standard
+    };
+
+    let mut interpreter = Interpreter::new(InterpreterConfig {
+        stack_size,
+        output_mode,
+        ..Default::default()
+    });
 
-    interpret_forth_file(&args[1], &mut stack, &mut words_dictionary);
+    match filename {
+        Some(filename) => interpret_forth_file(filename, &mut interpreter),
+        None => run_repl(&mut interpreter),
+    }
 }
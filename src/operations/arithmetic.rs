@@ -1,9 +1,5 @@
 use super::forth_operation::ForthOperation;
-use crate::{
-    errors::{ForthError, print_error},
-    forth_value::ForthValue,
-    stack::Stack,
-};
+use crate::{errors::ForthError, forth_value::ForthValue, stack::Stack};
 
 /// Enum that represents the arithmetic operations that can be interpreted by the program.
 ///
@@ -13,6 +9,14 @@ use crate::{
 /// - Substract: substract between two numeric elements.
 /// - Multiply: multiplication between two numeric elements.
 /// - Divide: division between two numeric elements.
+/// - Mod: remainder of the division between two numeric elements.
+/// - DivMod: division between two numeric elements, leaving the remainder and the quotient ('/MOD').
+/// - StarSlash: multiplication of two numeric elements divided by a third one, with a double-width intermediate result ('*/').
+/// - StarSlashMod: like StarSlash, but leaving the remainder and the quotient ('*/MOD').
+/// - Negate: changes the sign of a numeric element.
+/// - Abs: absolute value of a numeric element.
+/// - Min: the smallest of two numeric elements.
+/// - Max: the greatest of two numeric elements.
 ///
 
 #[derive(Debug)]
@@ -21,120 +25,253 @@ pub enum ArithmeticOperation {
     Substract,
     Multiply,
     Divide,
+    Mod,
+    DivMod,
+    StarSlash,
+    StarSlashMod,
+    Negate,
+    Abs,
+    Min,
+    Max,
 }
 
 ///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
 pub fn parse_arithmetic(token: &str) -> Option<ForthValue> {
-    match token {
-        "+" => Some(ForthValue::Operation(ForthOperation::Arithmetic(
-            ArithmeticOperation::Add,
-        ))),
-        "-" => Some(ForthValue::Operation(ForthOperation::Arithmetic(
-            ArithmeticOperation::Substract,
-        ))),
-        "*" => Some(ForthValue::Operation(ForthOperation::Arithmetic(
-            ArithmeticOperation::Multiply,
-        ))),
-        "/" => Some(ForthValue::Operation(ForthOperation::Arithmetic(
-            ArithmeticOperation::Divide,
-        ))),
-        _ => None,
-    }
+    let op = match token {
+        "+" => ArithmeticOperation::Add,
+        "-" => ArithmeticOperation::Substract,
+        "*" => ArithmeticOperation::Multiply,
+        "/" => ArithmeticOperation::Divide,
+        "MOD" => ArithmeticOperation::Mod,
+        "/MOD" => ArithmeticOperation::DivMod,
+        "*/" => ArithmeticOperation::StarSlash,
+        "*/MOD" => ArithmeticOperation::StarSlashMod,
+        "NEGATE" => ArithmeticOperation::Negate,
+        "ABS" => ArithmeticOperation::Abs,
+        "MIN" => ArithmeticOperation::Min,
+        "MAX" => ArithmeticOperation::Max,
+        _ => return None,
+    };
+    Some(ForthValue::Operation(ForthOperation::Arithmetic(op)))
 }
 
 ///Function that executes an arithmetic operation by receiving a reference to an arithmetic operation and the stack reference as mutable so it can be updated with the result.
-pub fn execute_arithmetic_op(op: &ArithmeticOperation, stack: &mut Stack) {
-    let a = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
+/// The result is computed with a wider type and then fitted in a cell by the overflow policy of the stack (see Stack::push_wide), and the divisions are rounded by its DivisionMode.
+/// Returns the error of the first operand that could not be popped, division-by-zero, or the error of pushing the result (such as overflow).
+pub fn execute_arithmetic_op(
+    op: &ArithmeticOperation,
+    stack: &mut Stack,
+) -> Result<(), ForthError> {
+    match op {
+        ArithmeticOperation::Negate => {
+            let a = i128::from(stack.pop()?);
+            stack.push_wide(-a)
         }
-    };
-    let b = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
+        ArithmeticOperation::Abs => {
+            let a = i128::from(stack.pop()?);
+            stack.push_wide(a.abs())
         }
-    };
+        ArithmeticOperation::StarSlash | ArithmeticOperation::StarSlashMod => {
+            let divisor = i128::from(stack.pop()?);
+            let b = i128::from(stack.pop()?);
+            let a = i128::from(stack.pop()?);
+            let (remainder, quotient) = stack.cell().divide(a * b, divisor)?;
+            if let ArithmeticOperation::StarSlashMod = op {
+                stack.push_wide(remainder)?;
+            }
+            stack.push_wide(quotient)
+        }
+        _ => execute_binary_op(op, stack),
+    }
+}
+
+fn execute_binary_op(op: &ArithmeticOperation, stack: &mut Stack) -> Result<(), ForthError> {
+    let a = i128::from(stack.pop()?);
+    let b = i128::from(stack.pop()?);
     let result = match op {
         ArithmeticOperation::Add => a + b,
         ArithmeticOperation::Substract => b - a,
         ArithmeticOperation::Multiply => a * b,
-        ArithmeticOperation::Divide => {
-            if a != 0 {
-                b / a
-            } else {
-                print_error(ForthError::DivisionByZero);
-                return;
-            }
+        ArithmeticOperation::Divide => stack.cell().divide(b, a)?.1,
+        ArithmeticOperation::Mod => stack.cell().divide(b, a)?.0,
+        ArithmeticOperation::DivMod => {
+            let (remainder, quotient) = stack.cell().divide(b, a)?;
+            stack.push_wide(remainder)?;
+            quotient
         }
+        ArithmeticOperation::Min => a.min(b),
+        _ => a.max(b),
     };
-    if let Err(e) = stack.push(result) {
-        print_error(e);
-    }
+    stack.push_wide(result)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::init_stack;
+    use crate::{
+        cell::{CellConfig, CellWidth, DivisionMode, OverflowPolicy},
+        errors::ForthError,
+        stack::Stack,
+        utils::init_stack,
+    };
 
     use super::{ArithmeticOperation, execute_arithmetic_op};
 
     #[test]
     fn test_add_2() {
         let mut test_stack = init_stack(&[1, 2]);
-        execute_arithmetic_op(&ArithmeticOperation::Add, &mut test_stack);
+        execute_arithmetic_op(&ArithmeticOperation::Add, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[3]);
     }
 
     #[test]
     fn test_add_3() {
         let mut test_stack = init_stack(&[1, 2, 3]);
-        execute_arithmetic_op(&ArithmeticOperation::Add, &mut test_stack);
+        execute_arithmetic_op(&ArithmeticOperation::Add, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 5]);
     }
 
     #[test]
     fn test_sub_2() {
         let mut test_stack = init_stack(&[3, 4]);
-        execute_arithmetic_op(&ArithmeticOperation::Substract, &mut test_stack);
+        execute_arithmetic_op(&ArithmeticOperation::Substract, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
 
     #[test]
     fn test_sub_3() {
         let mut test_stack = init_stack(&[1, 12, 3]);
-        execute_arithmetic_op(&ArithmeticOperation::Substract, &mut test_stack);
+        execute_arithmetic_op(&ArithmeticOperation::Substract, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 9]);
     }
 
     #[test]
     fn test_mul_2() {
         let mut test_stack = init_stack(&[2, 4]);
-        execute_arithmetic_op(&ArithmeticOperation::Multiply, &mut test_stack);
+        execute_arithmetic_op(&ArithmeticOperation::Multiply, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[8]);
     }
 
     #[test]
     fn test_mul_3() {
         let mut test_stack = init_stack(&[1, 2, 3]);
-        execute_arithmetic_op(&ArithmeticOperation::Multiply, &mut test_stack);
+        execute_arithmetic_op(&ArithmeticOperation::Multiply, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 6]);
     }
 
     #[test]
     fn test_divide_2() {
         let mut test_stack = init_stack(&[12, 3]);
-        execute_arithmetic_op(&ArithmeticOperation::Divide, &mut test_stack);
+        execute_arithmetic_op(&ArithmeticOperation::Divide, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[4]);
     }
 
     #[test]
     fn test_divide_3() {
         let mut test_stack = init_stack(&[1, 12, 3]);
-        execute_arithmetic_op(&ArithmeticOperation::Divide, &mut test_stack);
+        execute_arithmetic_op(&ArithmeticOperation::Divide, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 4]);
     }
+
+    #[test]
+    fn test_overflow_policies() {
+        let mut test_stack = init_stack(&[32767, 1]);
+        execute_arithmetic_op(&ArithmeticOperation::Add, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-32768]);
+
+        let cell = CellConfig {
+            overflow: OverflowPolicy::Saturating,
+            ..Default::default()
+        };
+        let mut test_stack = Stack::with_cells(100, cell);
+        test_stack.data.extend([300, 300]);
+        execute_arithmetic_op(&ArithmeticOperation::Multiply, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[32767]);
+
+        let cell = CellConfig {
+            overflow: OverflowPolicy::Error,
+            ..Default::default()
+        };
+        let mut test_stack = Stack::with_cells(100, cell);
+        test_stack.data.extend([-32768, -1]);
+        assert_eq!(
+            execute_arithmetic_op(&ArithmeticOperation::Divide, &mut test_stack),
+            Err(ForthError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_wider_cells() {
+        let cell = CellConfig {
+            width: CellWidth::Bits32,
+            overflow: OverflowPolicy::Error,
+            ..Default::default()
+        };
+        let mut test_stack = Stack::with_cells(100, cell);
+        test_stack.data.extend([300, 300]);
+        execute_arithmetic_op(&ArithmeticOperation::Multiply, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[90000]);
+    }
+
+    #[test]
+    fn test_mod_and_div_mod() {
+        let mut test_stack = init_stack(&[13, 5]);
+        execute_arithmetic_op(&ArithmeticOperation::Mod, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[3]);
+
+        let mut test_stack = init_stack(&[-7, 2]);
+        execute_arithmetic_op(&ArithmeticOperation::DivMod, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-1, -3]);
+
+        let mut test_stack = init_stack(&[1, 0]);
+        assert_eq!(
+            execute_arithmetic_op(&ArithmeticOperation::Mod, &mut test_stack),
+            Err(ForthError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_floored_division() {
+        let cell = CellConfig {
+            division: DivisionMode::Floored,
+            ..Default::default()
+        };
+        let mut test_stack = Stack::with_cells(100, cell);
+        test_stack.data.extend([-7, 2, -7, 2]);
+        execute_arithmetic_op(&ArithmeticOperation::Divide, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-7, 2, -4]);
+        test_stack.data.truncate(2);
+        execute_arithmetic_op(&ArithmeticOperation::DivMod, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[1, -4]);
+    }
+
+    #[test]
+    fn test_star_slash() {
+        let mut test_stack = init_stack(&[30000, 3, 1000]);
+        execute_arithmetic_op(&ArithmeticOperation::StarSlash, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[90]);
+
+        let mut test_stack = init_stack(&[1000, 7, 3]);
+        execute_arithmetic_op(&ArithmeticOperation::StarSlashMod, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[1, 2333]);
+
+        let mut test_stack = init_stack(&[1, 2, 0]);
+        assert_eq!(
+            execute_arithmetic_op(&ArithmeticOperation::StarSlash, &mut test_stack),
+            Err(ForthError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_negate_abs_min_max() {
+        let mut test_stack = init_stack(&[5, -3]);
+        execute_arithmetic_op(&ArithmeticOperation::Negate, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[5, 3]);
+        execute_arithmetic_op(&ArithmeticOperation::Min, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[3]);
+
+        let mut test_stack = init_stack(&[-5, -3]);
+        execute_arithmetic_op(&ArithmeticOperation::Max, &mut test_stack).unwrap();
+        execute_arithmetic_op(&ArithmeticOperation::Abs, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[3]);
+    }
 }
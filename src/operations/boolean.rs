@@ -1,103 +1,135 @@
 use super::forth_operation::ForthOperation;
-use crate::{
-    errors::{ForthError, print_error},
-    forth_value::ForthValue,
-    stack::Stack,
-};
+use crate::{errors::ForthError, forth_value::ForthValue, stack::Stack};
 
-/// Enum that represents the boolean operations that can be interpreted by the program.
+/// Enum that represents the boolean operations (comparisons and bitwise operations) that can be interpreted by the program.
 ///
 /// The different ones are:
 ///
 /// - Equal: checks if two numeric are equal.
+/// - NotEqual: checks if two numeric are different ('<>').
 /// - Less: checks if one numeric element is less than the other one.
 /// - Greater: checks if one numeric element is greater than the other one.
-/// - And: checks if the condition between two expressions is true.
-/// - Or: checks if the condition either of two expressions is true.
-/// - Not: Denies a value.
+/// - UnsignedLess: checks if one numeric element is less than the other one, reading both as unsigned cells ('U<').
+/// - ZeroEqual: checks if a numeric element is zero ('0=').
+/// - ZeroLess: checks if a numeric element is negative ('0<').
+/// - ZeroGreater: checks if a numeric element is positive ('0>').
+/// - And: bitwise and between two numeric elements.
+/// - Or: bitwise or between two numeric elements.
+/// - Xor: bitwise exclusive or between two numeric elements.
+/// - Invert: inverts every bit of a numeric element.
+/// - LeftShift: shifts the bits of a numeric element to the left by the amount of the other one ('LSHIFT').
+/// - RightShift: shifts the bits of a numeric element to the right by the amount of the other one, filling with zeros ('RSHIFT').
+/// - Not: Denies a value (as '0=').
+///
+/// Compatibility note: AND and OR used to be logical tests (true only when the operands were -1), and now work on every bit of the cells as in standard Forth.
+/// Both give the same result for the flags the comparisons leave (-1 and 0), but not for other values: '3 1 AND' gave 0 and now gives 1.
+/// Code that used them as logical operators on any number should turn the numbers into flags first (for example with '0= 0=').
 ///
 
 #[derive(Debug)]
 pub enum BooleanOperation {
     Equal,
+    NotEqual,
     Less,
     Greater,
+    UnsignedLess,
+    ZeroEqual,
+    ZeroLess,
+    ZeroGreater,
     And,
     Or,
+    Xor,
+    Invert,
+    LeftShift,
+    RightShift,
     Not,
 }
 
 ///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
 pub fn parse_boolean(token: &str) -> Option<ForthValue> {
-    match token {
-        "=" => Some(ForthValue::Operation(ForthOperation::Boolean(
-            BooleanOperation::Equal,
-        ))),
-        "<" => Some(ForthValue::Operation(ForthOperation::Boolean(
-            BooleanOperation::Less,
-        ))),
-        ">" => Some(ForthValue::Operation(ForthOperation::Boolean(
-            BooleanOperation::Greater,
-        ))),
-        "AND" => Some(ForthValue::Operation(ForthOperation::Boolean(
-            BooleanOperation::And,
-        ))),
-        "OR" => Some(ForthValue::Operation(ForthOperation::Boolean(
-            BooleanOperation::Or,
-        ))),
-        "NOT" => Some(ForthValue::Operation(ForthOperation::Boolean(
-            BooleanOperation::Not,
-        ))),
-        _ => None,
-    }
+    let op = match token {
+        "=" => BooleanOperation::Equal,
+        "<>" => BooleanOperation::NotEqual,
+        "<" => BooleanOperation::Less,
+        ">" => BooleanOperation::Greater,
+        "U<" => BooleanOperation::UnsignedLess,
+        "0=" => BooleanOperation::ZeroEqual,
+        "0<" => BooleanOperation::ZeroLess,
+        "0>" => BooleanOperation::ZeroGreater,
+        "AND" => BooleanOperation::And,
+        "OR" => BooleanOperation::Or,
+        "XOR" => BooleanOperation::Xor,
+        "INVERT" => BooleanOperation::Invert,
+        "LSHIFT" => BooleanOperation::LeftShift,
+        "RSHIFT" => BooleanOperation::RightShift,
+        "NOT" => BooleanOperation::Not,
+        _ => return None,
+    };
+    Some(ForthValue::Operation(ForthOperation::Boolean(op)))
 }
 
 ///Function that executes a boolean operation by receiving a reference to a boolean operation and the stack reference as mutable so it can be updated with the result.
-pub fn execute_boolean_op(op: &BooleanOperation, stack: &mut Stack) {
+/// The comparisons push -1 (true) or 0 (false), and the bitwise operations work on the bits of the width of the cells of the stack (so '1 15 LSHIFT' gives -32768 with 16 bits cells).
+/// Returns the error of the first value that could not be popped or pushed.
+pub fn execute_boolean_op(op: &BooleanOperation, stack: &mut Stack) -> Result<(), ForthError> {
     match op {
-        BooleanOperation::Not => handle_not_operation(stack),
+        BooleanOperation::Not
+        | BooleanOperation::ZeroEqual
+        | BooleanOperation::ZeroLess
+        | BooleanOperation::ZeroGreater
+        | BooleanOperation::Invert => handle_unary_operation(op, stack),
+        BooleanOperation::LeftShift | BooleanOperation::RightShift => handle_shift(op, stack),
         _ => handle_other_boolean_ops(op, stack),
     }
 }
 
-fn handle_not_operation(stack: &mut Stack) {
-    match stack.pop() {
-        Ok(a) => {
-            let result = if a != 0 { 0 } else { -1 };
-            push_boolean_result(stack, result);
-        }
-        Err(e) => print_error(e),
+fn handle_unary_operation(op: &BooleanOperation, stack: &mut Stack) -> Result<(), ForthError> {
+    let a = stack.pop()?;
+    match op {
+        BooleanOperation::Invert => stack.push(!a),
+        BooleanOperation::ZeroLess => push_boolean_result(stack, a < 0),
+        BooleanOperation::ZeroGreater => push_boolean_result(stack, a > 0),
+        _ => push_boolean_result(stack, a == 0),
     }
 }
 
-fn handle_other_boolean_ops(op: &BooleanOperation, stack: &mut Stack) {
-    let (a, b) = match (stack.pop(), stack.pop()) {
-        (Ok(a), Ok(b)) => (a, b),
-        (Err(e), _) | (_, Err(e)) => {
-            print_error(e);
-            return;
-        }
+fn handle_shift(op: &BooleanOperation, stack: &mut Stack) -> Result<(), ForthError> {
+    let amount = stack.pop()?;
+    let value = stack.pop()?;
+    let cell = stack.cell();
+    let result = match u32::try_from(amount) {
+        Ok(amount) if amount < cell.bits() => match op {
+            BooleanOperation::LeftShift => cell.wrap(i128::from(value) << amount),
+            _ => cell.wrap((cell.unsigned(value) >> amount) as i128),
+        },
+        _ => 0,
     };
+    stack.push(result)
+}
+
+fn handle_other_boolean_ops(op: &BooleanOperation, stack: &mut Stack) -> Result<(), ForthError> {
+    let a = stack.pop()?;
+    let b = stack.pop()?;
 
     let result = match op {
+        BooleanOperation::And => return stack.push(a & b),
+        BooleanOperation::Or => return stack.push(a | b),
+        BooleanOperation::Xor => return stack.push(a ^ b),
         BooleanOperation::Equal => a == b,
+        BooleanOperation::NotEqual => a != b,
         BooleanOperation::Greater => a < b,
         BooleanOperation::Less => a > b,
-        BooleanOperation::And => a == -1 && b == -1,
-        BooleanOperation::Or => a == -1 || b == -1,
+        BooleanOperation::UnsignedLess => stack.cell().unsigned(b) < stack.cell().unsigned(a),
         _ => {
-            print_error(ForthError::Generic("Unknown boolean operation".to_string()));
-            false
+            return Err(ForthError::Generic("Unknown boolean operation".to_string()));
         }
     };
 
-    push_boolean_result(stack, if result { -1 } else { 0 });
+    push_boolean_result(stack, result)
 }
 
-fn push_boolean_result(stack: &mut Stack, value: i16) {
-    if let Err(e) = stack.push(value) {
-        print_error(e);
-    }
+fn push_boolean_result(stack: &mut Stack, result: bool) -> Result<(), ForthError> {
+    stack.push(if result { -1 } else { 0 })
 }
 
 #[cfg(test)]
@@ -108,120 +140,186 @@ mod tests {
     #[test]
     fn test_equals_true() {
         let mut test_stack = init_stack(&[1, 1]);
-        execute_boolean_op(&BooleanOperation::Equal, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Equal, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
 
     #[test]
     fn test_equals_false() {
         let mut test_stack = init_stack(&[1, 2]);
-        execute_boolean_op(&BooleanOperation::Equal, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Equal, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_less_true() {
         let mut test_stack = init_stack(&[1, 2]);
-        execute_boolean_op(&BooleanOperation::Less, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Less, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
 
     #[test]
     fn test_less_false() {
         let mut test_stack = init_stack(&[2, 1]);
-        execute_boolean_op(&BooleanOperation::Less, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Less, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_less_equals() {
         let mut test_stack = init_stack(&[2, 2]);
-        execute_boolean_op(&BooleanOperation::Less, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Less, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_greater_true() {
         let mut test_stack = init_stack(&[2, 1]);
-        execute_boolean_op(&BooleanOperation::Greater, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Greater, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
 
     #[test]
     fn test_greater_false() {
         let mut test_stack = init_stack(&[1, 2]);
-        execute_boolean_op(&BooleanOperation::Greater, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Greater, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_greater_equals() {
         let mut test_stack = init_stack(&[2, 2]);
-        execute_boolean_op(&BooleanOperation::Greater, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Greater, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_and_none() {
         let mut test_stack = init_stack(&[0, 0]);
-        execute_boolean_op(&BooleanOperation::And, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::And, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_and_one() {
         let mut test_stack = init_stack(&[-1, 0]);
-        execute_boolean_op(&BooleanOperation::And, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::And, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_and_both() {
         let mut test_stack = init_stack(&[-1, -1]);
-        execute_boolean_op(&BooleanOperation::And, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::And, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
 
     #[test]
     fn test_or_none() {
         let mut test_stack = init_stack(&[0, 0]);
-        execute_boolean_op(&BooleanOperation::Or, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Or, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_or_one() {
         let mut test_stack = init_stack(&[-1, 0]);
-        execute_boolean_op(&BooleanOperation::Or, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Or, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
 
     #[test]
     fn test_or_both() {
         let mut test_stack = init_stack(&[-1, -1]);
-        execute_boolean_op(&BooleanOperation::Or, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Or, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
 
     #[test]
     fn test_not_true() {
         let mut test_stack = init_stack(&[-1]);
-        execute_boolean_op(&BooleanOperation::Not, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Not, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[0]);
     }
 
     #[test]
     fn test_not_false() {
         let mut test_stack = init_stack(&[0]);
-        execute_boolean_op(&BooleanOperation::Not, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Not, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
 
     #[test]
     fn test_not_not() {
         let mut test_stack = init_stack(&[10]);
-        execute_boolean_op(&BooleanOperation::Not, &mut test_stack);
-        execute_boolean_op(&BooleanOperation::Not, &mut test_stack);
+        execute_boolean_op(&BooleanOperation::Not, &mut test_stack).unwrap();
+        execute_boolean_op(&BooleanOperation::Not, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[-1]);
     }
+
+    #[test]
+    fn test_bitwise_operations() {
+        let mut test_stack = init_stack(&[3, 1]);
+        execute_boolean_op(&BooleanOperation::And, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[1]);
+
+        let mut test_stack = init_stack(&[12, 10]);
+        execute_boolean_op(&BooleanOperation::Or, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[14]);
+
+        let mut test_stack = init_stack(&[12, 10]);
+        execute_boolean_op(&BooleanOperation::Xor, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[6]);
+
+        let mut test_stack = init_stack(&[0]);
+        execute_boolean_op(&BooleanOperation::Invert, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-1]);
+    }
+
+    #[test]
+    fn test_shifts() {
+        let mut test_stack = init_stack(&[1, 4]);
+        execute_boolean_op(&BooleanOperation::LeftShift, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[16]);
+
+        let mut test_stack = init_stack(&[1, 15]);
+        execute_boolean_op(&BooleanOperation::LeftShift, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-32768]);
+
+        let mut test_stack = init_stack(&[-1, 8]);
+        execute_boolean_op(&BooleanOperation::RightShift, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[255]);
+
+        let mut test_stack = init_stack(&[-1, 16]);
+        execute_boolean_op(&BooleanOperation::RightShift, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[0]);
+    }
+
+    #[test]
+    fn test_zero_comparisons() {
+        let mut test_stack = init_stack(&[0, -5, 5]);
+        execute_boolean_op(&BooleanOperation::ZeroGreater, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[0, -5, -1]);
+        test_stack.data.pop();
+        execute_boolean_op(&BooleanOperation::ZeroLess, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[0, -1]);
+        test_stack.data.pop();
+        execute_boolean_op(&BooleanOperation::ZeroEqual, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-1]);
+    }
+
+    #[test]
+    fn test_not_equal_and_unsigned_less() {
+        let mut test_stack = init_stack(&[1, 2]);
+        execute_boolean_op(&BooleanOperation::NotEqual, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-1]);
+
+        let mut test_stack = init_stack(&[1, -1]);
+        execute_boolean_op(&BooleanOperation::UnsignedLess, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-1]);
+
+        let mut test_stack = init_stack(&[-1, 1]);
+        execute_boolean_op(&BooleanOperation::UnsignedLess, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[0]);
+    }
 }
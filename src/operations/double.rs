@@ -0,0 +1,150 @@
+use super::forth_operation::ForthOperation;
+use crate::{errors::ForthError, forth_value::ForthValue, stack::Stack};
+
+/// Enum that represents the double-cell operations that can be interpreted by the program.
+/// A double-cell number takes two cells of the stack (its low cell, and its high cell on top), so it has twice the bits of a cell (32 bits with cells of 16 bits).
+///
+/// The different ones are:
+///
+/// - Add: sum between two double-cell numbers ('D+').
+/// - Substract: substract between two double-cell numbers ('D-').
+/// - Negate: changes the sign of a double-cell number ('DNEGATE').
+/// - Less: checks if one double-cell number is less than the other one ('D<').
+/// - MixedMultiply: multiplication between two numeric elements, with a double-cell result ('M*').
+/// - UnsignedMultiply: multiplication between two numeric elements read as unsigned cells, with an unsigned double-cell result ('UM*').
+/// - UnsignedDivMod: division of an unsigned double-cell number by a numeric element read as an unsigned cell, leaving the remainder and the quotient ('UM/MOD').
+///
+
+#[derive(Debug)]
+pub enum DoubleOperation {
+    Add,
+    Substract,
+    Negate,
+    Less,
+    MixedMultiply,
+    UnsignedMultiply,
+    UnsignedDivMod,
+}
+
+///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
+pub fn parse_double_op(token: &str) -> Option<ForthValue> {
+    let op = match token {
+        "D+" => DoubleOperation::Add,
+        "D-" => DoubleOperation::Substract,
+        "DNEGATE" => DoubleOperation::Negate,
+        "D<" => DoubleOperation::Less,
+        "M*" => DoubleOperation::MixedMultiply,
+        "UM*" => DoubleOperation::UnsignedMultiply,
+        "UM/MOD" => DoubleOperation::UnsignedDivMod,
+        _ => return None,
+    };
+    Some(ForthValue::Operation(ForthOperation::Double(op)))
+}
+
+///Function that executes a double-cell operation by receiving a reference to a double-cell operation and the stack reference as mutable so it can be updated with the result.
+/// The double-cell results wrap around when they do not fit in two cells (as in real Forth), no matter the overflow policy of the stack, and so does the quotient of 'UM/MOD' when it does not fit in a cell.
+/// Returns the error of the first value that could not be popped or pushed, or division-by-zero.
+pub fn execute_double_op(op: &DoubleOperation, stack: &mut Stack) -> Result<(), ForthError> {
+    match op {
+        DoubleOperation::Add | DoubleOperation::Substract | DoubleOperation::Less => {
+            let a = stack.pop_double()?;
+            let b = stack.pop_double()?;
+            match op {
+                DoubleOperation::Add => stack.push_double(b.wrapping_add(a)),
+                DoubleOperation::Substract => stack.push_double(b.wrapping_sub(a)),
+                _ => stack.push(if b < a { -1 } else { 0 }),
+            }
+        }
+        DoubleOperation::Negate => {
+            let a = stack.pop_double()?;
+            stack.push_double(a.wrapping_neg())
+        }
+        DoubleOperation::MixedMultiply => {
+            let a = i128::from(stack.pop()?);
+            let b = i128::from(stack.pop()?);
+            stack.push_double(a.wrapping_mul(b))
+        }
+        DoubleOperation::UnsignedMultiply => {
+            let a = stack.pop()?;
+            let b = stack.pop()?;
+            let product = stack.cell().unsigned(a) * stack.cell().unsigned(b);
+            stack.push_double(product as i128)
+        }
+        DoubleOperation::UnsignedDivMod => handle_unsigned_div_mod(stack),
+    }
+}
+
+fn handle_unsigned_div_mod(stack: &mut Stack) -> Result<(), ForthError> {
+    let divisor = stack.pop()?;
+    let dividend = stack.pop_double()?;
+    let (dividend, divisor) = (
+        stack.cell().unsigned_double(dividend),
+        stack.cell().unsigned(divisor),
+    );
+    if divisor == 0 {
+        return Err(ForthError::DivisionByZero);
+    }
+    let remainder = stack.cell().wrap((dividend % divisor) as i128);
+    let quotient = stack.cell().wrap((dividend / divisor) as i128);
+    stack.push(remainder)?;
+    stack.push(quotient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DoubleOperation, execute_double_op};
+    use crate::{errors::ForthError, utils::init_stack};
+
+    #[test]
+    fn test_add_with_carry() {
+        let mut test_stack = init_stack(&[-1, 0, 1, 0]);
+        execute_double_op(&DoubleOperation::Add, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[0, 1]);
+    }
+
+    #[test]
+    fn test_sub_with_borrow() {
+        let mut test_stack = init_stack(&[0, 1, 1, 0]);
+        execute_double_op(&DoubleOperation::Substract, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-1, 0]);
+    }
+
+    #[test]
+    fn test_negate_and_less() {
+        let mut test_stack = init_stack(&[5, 0]);
+        execute_double_op(&DoubleOperation::Negate, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-5, -1]);
+
+        test_stack.data.extend([0, 1]);
+        execute_double_op(&DoubleOperation::Less, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-1]);
+
+        let mut test_stack = init_stack(&[0, 1, -1, 0]);
+        execute_double_op(&DoubleOperation::Less, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[0]);
+    }
+
+    #[test]
+    fn test_multiplications() {
+        let mut test_stack = init_stack(&[1000, -1000]);
+        execute_double_op(&DoubleOperation::MixedMultiply, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-16960, -16]);
+
+        let mut test_stack = init_stack(&[-1, 2]);
+        execute_double_op(&DoubleOperation::UnsignedMultiply, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[-2, 1]);
+    }
+
+    #[test]
+    fn test_unsigned_div_mod() {
+        let mut test_stack = init_stack(&[-31072, 1, 7]);
+        execute_double_op(&DoubleOperation::UnsignedDivMod, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[5, 14285]);
+
+        let mut test_stack = init_stack(&[1, 0, 0]);
+        assert_eq!(
+            execute_double_op(&DoubleOperation::UnsignedDivMod, &mut test_stack),
+            Err(ForthError::DivisionByZero)
+        );
+    }
+}
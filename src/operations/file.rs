@@ -0,0 +1,190 @@
+use super::{forth_operation::ForthOperation, string::cells_to_string};
+use crate::{
+    cell::Cell,
+    context::ForthContext,
+    errors::ForthError,
+    file_table::{IOR_SUCCESS, READ_ONLY, READ_WRITE, WRITE_ONLY, ior_code},
+    forth_value::ForthValue,
+    stack::Stack,
+};
+
+/// Enum that represents the file operations that can be interpreted by the program, which use the files kept in the file table of the context.
+/// The operations that use a file push an ior code after their results (0 if they succeed), instead of stopping the program (see file_table).
+///
+/// The different ones are:
+///
+/// - AccessMethod: tuple that contains the access method to push ('R/O', 'W/O' and 'R/W').
+/// - OpenFile: opens the file named by a string with an access method, pushing its file id and the ior ('OPEN-FILE').
+/// - CreateFile: creates (or truncates) the file named by a string and opens it with an access method, pushing its file id and the ior ('CREATE-FILE').
+/// - CloseFile: closes the file of a file id, pushing the ior ('CLOSE-FILE').
+/// - ReadLine: reads a line of up to an amount of chars of a file into the cells of an address, pushing the amount of chars read, false if the end of the file was reached (or true otherwise) and the ior ('READ-LINE').
+/// - WriteFile: writes a string to a file, pushing the ior ('WRITE-FILE').
+/// - WriteLine: writes a string and a newline to a file, pushing the ior ('WRITE-LINE').
+///
+
+#[derive(Debug)]
+pub enum FileOperation {
+    AccessMethod(Cell),
+    OpenFile,
+    CreateFile,
+    CloseFile,
+    ReadLine,
+    WriteFile,
+    WriteLine,
+}
+
+///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
+pub fn parse_file_op(token: &str) -> Option<ForthValue> {
+    let op = match token {
+        "R/O" => FileOperation::AccessMethod(READ_ONLY),
+        "W/O" => FileOperation::AccessMethod(WRITE_ONLY),
+        "R/W" => FileOperation::AccessMethod(READ_WRITE),
+        "OPEN-FILE" => FileOperation::OpenFile,
+        "CREATE-FILE" => FileOperation::CreateFile,
+        "CLOSE-FILE" => FileOperation::CloseFile,
+        "READ-LINE" => FileOperation::ReadLine,
+        "WRITE-FILE" => FileOperation::WriteFile,
+        "WRITE-LINE" => FileOperation::WriteLine,
+        _ => return None,
+    };
+    Some(ForthValue::Operation(ForthOperation::File(op)))
+}
+
+///Function that executes a file operation by receiving a reference to a file operation, the stack reference as mutable so it can be updated with the result,
+/// and the context, whose file table keeps the open files and whose data memory keeps the names and the lines.
+/// Returns the error of the first value that could not be popped or pushed, or invalid-address if a string or a line is not in the allocated cells.
+/// The errors of the files themselves are pushed as ior codes.
+pub fn execute_file_op(
+    op: &FileOperation,
+    stack: &mut Stack,
+    context: &mut ForthContext,
+) -> Result<(), ForthError> {
+    match op {
+        FileOperation::AccessMethod(access) => stack.push(*access),
+        FileOperation::OpenFile | FileOperation::CreateFile => {
+            let access = stack.pop()?;
+            let length = stack.pop()?;
+            let address = stack.pop()?;
+            let name = cells_to_string(context.memory.fetch_range(address, length)?);
+            let create = matches!(op, FileOperation::CreateFile);
+            match context.files.open(&name, access, create) {
+                Ok(id) => {
+                    stack.push(id)?;
+                    stack.push(IOR_SUCCESS)
+                }
+                Err(error) => {
+                    stack.push(0)?;
+                    stack.push(ior_code(&error))
+                }
+            }
+        }
+        FileOperation::CloseFile => {
+            let id = stack.pop()?;
+            let result = context.files.close(id);
+            stack.push(result.map_or_else(|error| ior_code(&error), |_| IOR_SUCCESS))
+        }
+        FileOperation::ReadLine => handle_read_line(stack, context),
+        FileOperation::WriteFile | FileOperation::WriteLine => {
+            let id = stack.pop()?;
+            let length = stack.pop()?;
+            let address = stack.pop()?;
+            let mut bytes: Vec<u8> = context
+                .memory
+                .fetch_range(address, length)?
+                .iter()
+                .map(|&code| code as u8)
+                .collect();
+            if matches!(op, FileOperation::WriteLine) {
+                bytes.push(b'\n');
+            }
+            let result = context.files.write(id, &bytes);
+            stack.push(result.map_or_else(|error| ior_code(&error), |_| IOR_SUCCESS))
+        }
+    }
+}
+
+/// Function that executes 'READ-LINE' ( c-addr u1 fileid -- u2 flag ior ) with the Stack and the context received by parameter.
+/// The cells for the max amount of chars must be allocated before anything is read.
+fn handle_read_line(stack: &mut Stack, context: &mut ForthContext) -> Result<(), ForthError> {
+    let id = stack.pop()?;
+    let max_length = stack.pop()?.max(0);
+    let address = stack.pop()?;
+    context.memory.fetch_range(address, max_length)?;
+    let (length, flag, ior) = match context.files.read_line(id, max_length as usize) {
+        Ok(Some(line)) => {
+            for (offset, &code) in line.iter().enumerate() {
+                context
+                    .memory
+                    .store(address + offset as Cell, Cell::from(code))?;
+            }
+            (line.len() as Cell, -1, IOR_SUCCESS)
+        }
+        Ok(None) => (0, 0, IOR_SUCCESS),
+        Err(error) => (0, 0, ior_code(&error)),
+    };
+    stack.push(length)?;
+    stack.push(flag)?;
+    stack.push(ior)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileOperation, execute_file_op};
+    use crate::{
+        context::ForthContext,
+        file_table::{FileTable, IOR_FILE_IO, IOR_NON_EXISTENT_FILE},
+        utils::init_stack,
+    };
+    use std::fs;
+
+    #[test]
+    fn test_file_operations() {
+        let root = std::env::temp_dir().join("forth_file_operations_test");
+        fs::create_dir_all(&root).unwrap();
+        let mut context = ForthContext {
+            files: FileTable::new(root.to_str().unwrap()),
+            ..Default::default()
+        };
+        context.memory.store_string("data.txtok").unwrap();
+
+        let mut test_stack = init_stack(&[0, 8, 1]);
+        execute_file_op(&FileOperation::CreateFile, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[1, 0]);
+        test_stack.data = vec![8, 2, 1];
+        execute_file_op(&FileOperation::WriteLine, &mut test_stack, &mut context).unwrap();
+        test_stack.push(1).unwrap();
+        execute_file_op(&FileOperation::CloseFile, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[0, 0]);
+        assert_eq!(fs::read_to_string(root.join("data.txt")).unwrap(), "ok\n");
+
+        let mut test_stack = init_stack(&[0, 8, 0]);
+        execute_file_op(&FileOperation::OpenFile, &mut test_stack, &mut context).unwrap();
+        test_stack.data = vec![0, 4, 1];
+        execute_file_op(&FileOperation::ReadLine, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[2, -1, 0]);
+        assert_eq!(&context.memory.cells[..4], &[111, 107, 116, 97]);
+        test_stack.data = vec![0, 4, 1];
+        execute_file_op(&FileOperation::ReadLine, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[0, 0, 0]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_file_errors() {
+        let mut context = ForthContext::default();
+        context.memory.store_string("../x").unwrap();
+
+        let mut test_stack = init_stack(&[0, 4, 0]);
+        execute_file_op(&FileOperation::OpenFile, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[0, IOR_NON_EXISTENT_FILE]);
+
+        let mut test_stack = init_stack(&[0, 4, 7]);
+        execute_file_op(&FileOperation::ReadLine, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[0, 0, IOR_FILE_IO]);
+
+        let mut test_stack = init_stack(&[0, 4, 7]);
+        execute_file_op(&FileOperation::WriteFile, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[IOR_FILE_IO]);
+    }
+}
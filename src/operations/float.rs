@@ -0,0 +1,246 @@
+use super::{forth_operation::ForthOperation, output::number_separator};
+use crate::{context::ForthContext, errors::ForthError, forth_value::ForthValue, stack::Stack};
+
+/// Enum that represents the floating-point operations that can be interpreted by the program (only with the "float" feature).
+/// They work on the values of the float stack, apart from the cells of the stack, which only keeps the flags and the integers they are converted from or to.
+///
+/// The different ones are:
+///
+/// - Add: sum between two float elements ('F+').
+/// - Substract: substract between two float elements ('F-').
+/// - Multiply: multiplication between two float elements ('F*').
+/// - Divide: division between two float elements ('F/').
+/// - Dot: pops a float element and prints it ('F.').
+/// - Duplicate: duplicate the last float element ('FDUP').
+/// - Drop: removes the last float element ('FDROP').
+/// - Swap: change positions between the last two float elements ('FSWAP').
+/// - Less: checks if one float element is less than the other one, pushing the flag to the stack ('F<').
+/// - ZeroEqual: checks if a float element is zero, pushing the flag to the stack ('F0=').
+/// - FromInteger: converts a numeric element of the stack to a float element ('S>F').
+/// - ToInteger: converts a float element to a numeric element of the stack, truncating it ('F>S').
+/// - FromDouble: converts a double-cell number of the stack to a float element ('D>F').
+/// - ToDouble: converts a float element to a double-cell number of the stack, truncating it ('F>D').
+///
+
+#[derive(Debug)]
+pub enum FloatOperation {
+    Add,
+    Substract,
+    Multiply,
+    Divide,
+    Dot,
+    Duplicate,
+    Drop,
+    Swap,
+    Less,
+    ZeroEqual,
+    FromInteger,
+    ToInteger,
+    FromDouble,
+    ToDouble,
+}
+
+///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
+pub fn parse_float_op(token: &str) -> Option<ForthValue> {
+    let op = match token {
+        "F+" => FloatOperation::Add,
+        "F-" => FloatOperation::Substract,
+        "F*" => FloatOperation::Multiply,
+        "F/" => FloatOperation::Divide,
+        "F." => FloatOperation::Dot,
+        "FDUP" => FloatOperation::Duplicate,
+        "FDROP" => FloatOperation::Drop,
+        "FSWAP" => FloatOperation::Swap,
+        "F<" => FloatOperation::Less,
+        "F0=" => FloatOperation::ZeroEqual,
+        "S>F" => FloatOperation::FromInteger,
+        "F>S" => FloatOperation::ToInteger,
+        "D>F" => FloatOperation::FromDouble,
+        "F>D" => FloatOperation::ToDouble,
+        _ => return None,
+    };
+    Some(ForthValue::Operation(ForthOperation::Float(op)))
+}
+
+/// Function that parses a float literal received by parameter as &str, which is a decimal number with an exponent (as '1.5e0', '-2E3' or '1e', which is 1.0).
+/// The float literals are only read when the number base is decimal, since in other bases the exponent could be a digit.
+/// Returns None if the token is not a float literal.
+pub fn parse_float(token: &str, base: u32) -> Option<f64> {
+    let starts_as_number = token
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.'));
+    if base != 10 || !starts_as_number || !token.contains(['e', 'E']) {
+        return None;
+    }
+    match token.strip_suffix(['e', 'E']) {
+        Some(mantissa) => format!("{mantissa}e0").parse().ok(),
+        None => token.parse().ok(),
+    }
+}
+
+///Function that executes a floating-point operation by receiving a reference to a floating-point operation, the stack reference as mutable (for the flags and the conversions),
+/// and the context, whose float stack is updated with the result and whose output sink receives the text printed by 'F.'.
+/// Returns the error of the first value that could not be popped or pushed (in the float stack or the stack), division-by-zero,
+/// or overflow if a float element that is not finite is converted to an integer.
+pub fn execute_float_op(
+    op: &FloatOperation,
+    stack: &mut Stack,
+    context: &mut ForthContext,
+) -> Result<(), ForthError> {
+    let floats = &mut context.float_stack;
+    match op {
+        FloatOperation::Add
+        | FloatOperation::Substract
+        | FloatOperation::Multiply
+        | FloatOperation::Divide => {
+            let a = floats.pop()?;
+            let b = floats.pop()?;
+            let result = match op {
+                FloatOperation::Add => b + a,
+                FloatOperation::Substract => b - a,
+                FloatOperation::Multiply => b * a,
+                _ if a == 0.0 => return Err(ForthError::DivisionByZero),
+                _ => b / a,
+            };
+            floats.push(result)
+        }
+        FloatOperation::Dot => {
+            let text = format_float(floats.pop()?);
+            let separator = number_separator(&context.output_mode);
+            context.output.write_text(&format!("{text}{separator}"))
+        }
+        FloatOperation::Duplicate => floats.push(floats.peek()?),
+        FloatOperation::Drop => floats.pop().map(|_| ()),
+        FloatOperation::Swap => {
+            let a = floats.pop()?;
+            let b = floats.pop()?;
+            floats.push(a)?;
+            floats.push(b)
+        }
+        FloatOperation::Less => {
+            let a = floats.pop()?;
+            let b = floats.pop()?;
+            stack.push(if b < a { -1 } else { 0 })
+        }
+        FloatOperation::ZeroEqual => {
+            let a = floats.pop()?;
+            stack.push(if a == 0.0 { -1 } else { 0 })
+        }
+        FloatOperation::FromInteger => floats.push(stack.pop()? as f64),
+        FloatOperation::FromDouble => floats.push(stack.pop_double()? as f64),
+        FloatOperation::ToInteger => {
+            let value = truncate_float(floats.pop()?)?;
+            stack.push_wide(value)
+        }
+        FloatOperation::ToDouble => {
+            let value = truncate_float(floats.pop()?)?;
+            stack.push_double(value)
+        }
+    }
+}
+
+/// Function that truncates the float received by parameter to an integer. Returns overflow if it is not finite.
+fn truncate_float(value: f64) -> Result<i128, ForthError> {
+    if !value.is_finite() {
+        return Err(ForthError::Overflow);
+    }
+    Ok(value.trunc() as i128)
+}
+
+/// Function that returns the text printed by 'F.' for the float received by parameter. The integral values end with a '.' (as '2.'), so they are not confused with cells.
+pub fn format_float(value: f64) -> String {
+    if value.is_finite() && value.fract() == 0.0 {
+        format!("{value}.")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FloatOperation, execute_float_op, format_float, parse_float};
+    use crate::{
+        context::ForthContext, errors::ForthError, output_sink::BufferOutput, utils::init_stack,
+    };
+
+    fn float_context(values: &[f64]) -> ForthContext {
+        let mut context = ForthContext {
+            output: Box::new(BufferOutput::new()),
+            ..Default::default()
+        };
+        context.float_stack.data.extend(values);
+        context
+    }
+
+    #[test]
+    fn test_parse_float() {
+        assert_eq!(parse_float("1.5e0", 10), Some(1.5));
+        assert_eq!(parse_float("-2E3", 10), Some(-2000.0));
+        assert_eq!(parse_float("1e", 10), Some(1.0));
+        assert_eq!(parse_float("1.5", 10), None);
+        assert_eq!(parse_float("1e0", 16), None);
+        assert_eq!(parse_float("e5", 10), None);
+        assert_eq!(parse_float("inf", 10), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut test_stack = init_stack(&[]);
+        let mut context = float_context(&[1.5, 2.0, 4.0]);
+        execute_float_op(&FloatOperation::Multiply, &mut test_stack, &mut context).unwrap();
+        execute_float_op(&FloatOperation::Substract, &mut test_stack, &mut context).unwrap();
+        assert_eq!(context.float_stack.data, &[-6.5]);
+
+        let mut context = float_context(&[1.0, 0.0]);
+        assert_eq!(
+            execute_float_op(&FloatOperation::Divide, &mut test_stack, &mut context),
+            Err(ForthError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_stack_operations_and_comparisons() {
+        let mut test_stack = init_stack(&[]);
+        let mut context = float_context(&[1.0, 2.0]);
+        execute_float_op(&FloatOperation::Swap, &mut test_stack, &mut context).unwrap();
+        execute_float_op(&FloatOperation::Duplicate, &mut test_stack, &mut context).unwrap();
+        assert_eq!(context.float_stack.data, &[2.0, 1.0, 1.0]);
+        execute_float_op(&FloatOperation::Less, &mut test_stack, &mut context).unwrap();
+        execute_float_op(&FloatOperation::ZeroEqual, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[0, 0]);
+        assert!(context.float_stack.data.is_empty());
+        assert_eq!(
+            execute_float_op(&FloatOperation::Drop, &mut test_stack, &mut context),
+            Err(ForthError::FloatStackUnderflow)
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let mut test_stack = init_stack(&[-7, -31072, 1]);
+        let mut context = float_context(&[]);
+        execute_float_op(&FloatOperation::FromDouble, &mut test_stack, &mut context).unwrap();
+        execute_float_op(&FloatOperation::FromInteger, &mut test_stack, &mut context).unwrap();
+        assert_eq!(context.float_stack.data, &[100000.0, -7.0]);
+
+        context.float_stack.data.push(-2.75);
+        execute_float_op(&FloatOperation::ToInteger, &mut test_stack, &mut context).unwrap();
+        execute_float_op(&FloatOperation::Drop, &mut test_stack, &mut context).unwrap();
+        execute_float_op(&FloatOperation::ToDouble, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[-2, -31072, 1]);
+
+        context.float_stack.data.push(f64::NAN);
+        assert_eq!(
+            execute_float_op(&FloatOperation::ToInteger, &mut test_stack, &mut context),
+            Err(ForthError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_format_float() {
+        assert_eq!(format_float(2.0), "2.");
+        assert_eq!(format_float(-1.25), "-1.25");
+        assert_eq!(format_float(f64::INFINITY), "inf");
+    }
+}
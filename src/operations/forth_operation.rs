@@ -1,6 +1,8 @@
 use super::{
     arithmetic::ArithmeticOperation, boolean::BooleanOperation, conditional::ConditionalOperation,
-    output::OutputOperation, stack_type::StackOperation,
+    double::DoubleOperation, file::FileOperation, input::InputOperation, loops::LoopOperation,
+    memory::MemoryOperation, output::OutputOperation, return_stack_type::ReturnStackOperation,
+    stack_type::StackOperation, string::StringOperation,
 };
 
 /// Enum that represents the different operations that can be interpreted by the program.
@@ -11,7 +13,15 @@ use super::{
 /// - StackTypeOp: tuple that contains a stack-type operation.
 /// - Output: tuple that contains an output operation.
 /// - Boolean: tuple that contains a boolean operation.
+/// - Double: tuple that contains a double-cell operation.
+/// - Float: tuple that contains a floating-point operation (only with the "float" feature).
 /// - Conditional: tuple that contains a conditional operation.
+/// - Loop: tuple that contains a loop operation.
+/// - Memory: tuple that contains a memory operation.
+/// - ReturnStackOp: tuple that contains a return stack operation.
+/// - String: tuple that contains a string operation.
+/// - Input: tuple that contains an input operation.
+/// - File: tuple that contains a file operation.
 ///
 
 #[derive(Debug)]
@@ -20,5 +30,14 @@ pub enum ForthOperation {
     StackTypeOp(StackOperation),
     Output(OutputOperation),
     Boolean(BooleanOperation),
+    Double(DoubleOperation),
+    #[cfg(feature = "float")]
+    Float(super::float::FloatOperation),
     Conditional(ConditionalOperation),
+    Loop(LoopOperation),
+    Memory(MemoryOperation),
+    ReturnStackOp(ReturnStackOperation),
+    String(StringOperation),
+    Input(InputOperation),
+    File(FileOperation),
 }
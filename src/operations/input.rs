@@ -0,0 +1,153 @@
+use super::forth_operation::ForthOperation;
+use crate::{
+    cell::Cell, context::ForthContext, data_memory::SPAN_ADDRESS, errors::ForthError,
+    forth_value::ForthValue, stack::Stack,
+};
+
+/// Enum that represents the input operations that can be interpreted by the program, which read the chars of the input source of the context.
+///
+/// The different ones are:
+///
+/// - Key: reads the next char and pushes its code, or -1 if the end of the input was reached ('KEY').
+/// - KeyQuestion: pushes true if there is a char left to read, or false if the end of the input was reached ('KEY?'). Reading from the standard input, it waits until a char is typed or the input is closed.
+/// - Accept: reads a line of up to an amount of chars into the cells of an address, and pushes the amount of chars received ('ACCEPT').
+/// - Expect: reads a line of up to an amount of chars into the cells of an address, and stores the amount of chars received in SPAN ('EXPECT').
+/// - Span: pushes the address of the SPAN variable ('SPAN').
+///
+
+#[derive(Debug)]
+pub enum InputOperation {
+    Key,
+    KeyQuestion,
+    Accept,
+    Expect,
+    Span,
+}
+
+///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
+pub fn parse_input_op(token: &str) -> Option<ForthValue> {
+    let op = match token {
+        "KEY" => InputOperation::Key,
+        "KEY?" => InputOperation::KeyQuestion,
+        "ACCEPT" => InputOperation::Accept,
+        "EXPECT" => InputOperation::Expect,
+        "SPAN" => InputOperation::Span,
+        _ => return None,
+    };
+    Some(ForthValue::Operation(ForthOperation::Input(op)))
+}
+
+///Function that executes an input operation by receiving a reference to an input operation, the stack reference as mutable so it can be updated with the result,
+/// and the context, whose input source is read and whose data memory receives the lines.
+/// Returns the error of the first value that could not be popped or pushed, invalid-address if the cells of a line are not allocated, or the error of the input source.
+pub fn execute_input_op(
+    op: &InputOperation,
+    stack: &mut Stack,
+    context: &mut ForthContext,
+) -> Result<(), ForthError> {
+    match op {
+        InputOperation::Key => {
+            let code = context.input.read_char()?;
+            stack.push(code.map_or(-1, Cell::from))
+        }
+        InputOperation::KeyQuestion => {
+            let available = context.input.has_char()?;
+            stack.push(if available { -1 } else { 0 })
+        }
+        InputOperation::Accept => {
+            let length = read_line(stack, context)?;
+            stack.push(length)
+        }
+        InputOperation::Expect => {
+            let length = read_line(stack, context)?;
+            context.memory.store(SPAN_ADDRESS, length)
+        }
+        InputOperation::Span => stack.push(SPAN_ADDRESS),
+    }
+}
+
+/// Function that pops an address and a max amount of chars from the Stack received by parameter, and reads a line of the input source of the context into the cells of that address.
+/// The line ends with a newline (which is not stored, as the carriage returns), the end of the input, or when the max amount of chars is received.
+/// Returns the amount of chars stored, or invalid-address (before reading anything) if the cells for the max amount of chars are not allocated.
+fn read_line(stack: &mut Stack, context: &mut ForthContext) -> Result<Cell, ForthError> {
+    let max_length = stack.pop()?.max(0);
+    let address = stack.pop()?;
+    context.memory.fetch_range(address, max_length)?;
+    let mut length = 0;
+    while length < max_length {
+        match context.input.read_char()? {
+            None | Some(b'\n') => break,
+            Some(b'\r') => {}
+            Some(code) => {
+                context.memory.store(address + length, Cell::from(code))?;
+                length += 1;
+            }
+        }
+    }
+    Ok(length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputOperation, execute_input_op};
+    use crate::{
+        context::ForthContext, data_memory::SPAN_ADDRESS, errors::ForthError,
+        input_source::BufferInput, utils::init_stack,
+    };
+
+    fn input_context(text: &str) -> ForthContext {
+        let mut context = ForthContext {
+            input: Box::new(BufferInput::new(text)),
+            ..Default::default()
+        };
+        context.memory.allot(4).unwrap();
+        context
+    }
+
+    #[test]
+    fn test_key() {
+        let mut context = input_context("a");
+        let mut test_stack = init_stack(&[]);
+        for op in [
+            InputOperation::KeyQuestion,
+            InputOperation::Key,
+            InputOperation::KeyQuestion,
+            InputOperation::Key,
+        ] {
+            execute_input_op(&op, &mut test_stack, &mut context).unwrap();
+        }
+        assert_eq!(test_stack.data, &[-1, 97, 0, -1]);
+    }
+
+    #[test]
+    fn test_accept() {
+        let mut context = input_context("hi\r\nworld\n");
+        let mut test_stack = init_stack(&[0, 4]);
+        execute_input_op(&InputOperation::Accept, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[2]);
+        assert_eq!(context.memory.cells, &[104, 105, 0, 0]);
+
+        let mut test_stack = init_stack(&[0, 4]);
+        execute_input_op(&InputOperation::Accept, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[4]);
+        assert_eq!(context.memory.cells, &[119, 111, 114, 108]);
+
+        let mut test_stack = init_stack(&[2, 4]);
+        assert_eq!(
+            execute_input_op(&InputOperation::Accept, &mut test_stack, &mut context),
+            Err(ForthError::InvalidAddress)
+        );
+        assert_eq!(context.input.read_char().unwrap(), Some(b'd'));
+    }
+
+    #[test]
+    fn test_expect_and_span() {
+        let mut context = input_context("ok");
+        let mut test_stack = init_stack(&[1, 3]);
+        execute_input_op(&InputOperation::Expect, &mut test_stack, &mut context).unwrap();
+        execute_input_op(&InputOperation::Span, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[SPAN_ADDRESS]);
+        assert_eq!(context.memory.fetch(SPAN_ADDRESS), Ok(2));
+        assert_eq!(context.memory.cells, &[0, 111, 107, 0]);
+    }
+}
@@ -0,0 +1,369 @@
+use super::forth_operation::ForthOperation;
+use crate::{
+    cell::Cell,
+    errors::ForthError,
+    forth_value::ForthValue,
+    return_stack::{ReturnFrame, ReturnStack},
+    stack::Stack,
+};
+
+/// Enum that represents the loop operations that can be interpreted by the program.
+///
+/// The different ones are:
+///
+/// - Do: starts a counted loop, taking the limit and the initial index from the stack.
+/// - Loop: increments the index by one and jumps back to the start of the loop until the limit is reached.
+/// - PlusLoop: like Loop, but increments the index by the value on top of the stack.
+/// - Index: pushes the index of the innermost counted loop ('I').
+/// - OuterIndex: pushes the index of the next outer counted loop ('J').
+/// - Leave: sets the limit equal to the current index, so the loop ends on its next Loop or PlusLoop (as Forth-79 does).
+/// - Begin: starts an indefinite loop.
+/// - Until: jumps back to the start of the loop while the value on top of the stack is false (0).
+/// - While: continues the loop if the value on top of the stack is true, or jumps after its Repeat if it is false.
+/// - Repeat: jumps back to the start of the loop.
+/// - Again: jumps back to the start of the loop, without any condition.
+/// - Exit: stops the execution of the current body, which is the way to escape an endless loop.
+///
+
+#[derive(Debug)]
+pub enum LoopOperation {
+    Do,
+    Loop,
+    PlusLoop,
+    Index,
+    OuterIndex,
+    Leave,
+    Begin,
+    Until,
+    While,
+    Repeat,
+    Again,
+    Exit,
+}
+
+/// Enum that represents a loop that is being executed.
+///
+/// The different ones are:
+///
+/// - Counted: contains the current index and the limit of a loop started by 'DO'.
+///
+/// The loops are kept in the return stack while they are executed ('BEGIN' loops only need the jumps of the body, so they are not kept).
+#[derive(Debug)]
+pub enum LoopFrame {
+    Counted { index: Cell, limit: Cell },
+}
+
+///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
+pub fn parse_loop(token: &str) -> Option<ForthValue> {
+    match token {
+        "DO" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Do,
+        ))),
+        "LOOP" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Loop,
+        ))),
+        "+LOOP" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::PlusLoop,
+        ))),
+        "I" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Index,
+        ))),
+        "J" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::OuterIndex,
+        ))),
+        "LEAVE" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Leave,
+        ))),
+        "BEGIN" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Begin,
+        ))),
+        "UNTIL" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Until,
+        ))),
+        "WHILE" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::While,
+        ))),
+        "REPEAT" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Repeat,
+        ))),
+        "AGAIN" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Again,
+        ))),
+        "EXIT" => Some(ForthValue::Operation(ForthOperation::Loop(
+            LoopOperation::Exit,
+        ))),
+        _ => None,
+    }
+}
+
+///Function that executes a loop operation by receiving a reference to a loop operation and the stack reference as mutable so it can be updated with the result.
+/// It also receives the return stack, where the loops being executed are kept. Only the operations that do not jump ('I', 'J' and 'LEAVE') are executed here,
+/// since the rest are compiled to the instructions of the body they belong to.
+/// Returns invalid-loop if there is no loop on top of the return stack (or the operation is used outside a body), or the error of the value pushed.
+pub fn execute_loop_op(
+    op: &LoopOperation,
+    stack: &mut Stack,
+    return_stack: &mut ReturnStack,
+) -> Result<(), ForthError> {
+    match op {
+        LoopOperation::Index => push_loop_index(stack, return_stack, 0),
+        LoopOperation::OuterIndex => push_loop_index(stack, return_stack, 1),
+        LoopOperation::Leave => handle_leave(return_stack),
+        _ => Err(ForthError::InvalidLoop),
+    }
+}
+
+/// Function that starts a counted loop ('DO'), taking its initial index and limit from the Stack received by parameter and keeping it in the ReturnStack.
+/// Returns the error of the values popped, or return-stack-overflow if there is no room for the loop.
+pub fn handle_do(stack: &mut Stack, return_stack: &mut ReturnStack) -> Result<(), ForthError> {
+    let index = stack.pop()?;
+    let limit = stack.pop()?;
+    return_stack.push(ReturnFrame::Loop(LoopFrame::Counted { index, limit }))
+}
+
+/// Function that increments the index of the counted loop on top of the ReturnStack by the step received by parameter ('LOOP' and '+LOOP').
+/// Returns true if the loop has to be repeated, or false if it has finished (and it is removed from the ReturnStack).
+/// Returns invalid-loop if the top frame of the ReturnStack is not a counted loop.
+pub fn handle_loop_end(step: Cell, return_stack: &mut ReturnStack) -> Result<bool, ForthError> {
+    let Some(LoopFrame::Counted { index, limit }) = return_stack.last_loop_mut() else {
+        return Err(ForthError::InvalidLoop);
+    };
+    *index = index.wrapping_add(step);
+    let finished = if step >= 0 {
+        *index >= *limit
+    } else {
+        *index < *limit
+    };
+    if finished {
+        return_stack.pop_loop();
+    }
+    Ok(!finished)
+}
+
+fn handle_leave(return_stack: &mut ReturnStack) -> Result<(), ForthError> {
+    match return_stack.last_loop_mut() {
+        Some(LoopFrame::Counted { index, limit }) => {
+            *limit = *index;
+            Ok(())
+        }
+        None => Err(ForthError::InvalidLoop),
+    }
+}
+
+fn push_loop_index(
+    stack: &mut Stack,
+    return_stack: &ReturnStack,
+    depth: usize,
+) -> Result<(), ForthError> {
+    let index = return_stack
+        .loop_index(depth)
+        .ok_or(ForthError::InvalidLoop)?;
+    stack.push(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::ForthError,
+        stack::Stack,
+        tokens::{read_tokens, tokenize},
+        words::dictionary::WordsDictionary,
+    };
+
+    #[test]
+    fn test_do_loop_top_level() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(&tokenize("5 0 do i loop"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_do_loop_in_word() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize(": sum 0 swap 1 do i + loop ;"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+        read_tokens(&tokenize("5 sum"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[10]);
+    }
+
+    #[test]
+    fn test_do_executes_at_least_once() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(&tokenize("0 0 do 7 loop"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[7]);
+    }
+
+    #[test]
+    fn test_plus_loop() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(&tokenize("10 0 do i 3 +loop"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn test_plus_loop_negative() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(&tokenize("0 3 do i -1 +loop"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_nested_loops_i_j() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize(": pairs 3 1 do 3 1 do j 10 * i + loop loop ;"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+        read_tokens(&tokenize("pairs"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[11, 12, 21, 22]);
+    }
+
+    #[test]
+    fn test_leave() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize(": upto-3 10 0 do i dup 3 = if leave then loop ;"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+        read_tokens(&tokenize("upto-3"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_loop_inside_false_if() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize(": f if 3 0 do i loop then ;"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+        read_tokens(&tokenize("0 f -1 f"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[0, 1, 2]);
+    }
+
+    #[test]
+    fn test_index_outside_loop() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        for code in ["i", "j", "leave", "loop", "+loop", "until", "again"] {
+            assert_eq!(
+                read_tokens(&tokenize(code), &mut test_stack, &mut dict),
+                Err(ForthError::InvalidLoop)
+            );
+        }
+
+        assert!(test_stack.data.is_empty());
+    }
+
+    #[test]
+    fn test_begin_until() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize("1 begin dup 1 + dup 4 = until"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+
+        assert_eq!(test_stack.data, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_begin_while_repeat() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize(": countdown begin dup 0 > while dup 1 - repeat ;"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+        read_tokens(&tokenize("3 countdown"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_while_false_skips_nested_loops() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize(": f begin dup while 2 0 do i loop drop 0 repeat 9 ;"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+        read_tokens(&tokenize("0 f"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[0, 9]);
+    }
+
+    #[test]
+    fn test_begin_again_exit() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize(": f 0 begin 1 + dup 3 = if exit then again ;"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+        read_tokens(&tokenize("f"), &mut test_stack, &mut dict).unwrap();
+
+        assert_eq!(test_stack.data, &[3]);
+    }
+
+    #[test]
+    fn test_counted_loop_inside_begin() {
+        let mut dict = WordsDictionary::new();
+        let mut test_stack = Stack::new(100);
+
+        read_tokens(
+            &tokenize("2 begin 2 0 do i loop rot 1 - dup 0 = until"),
+            &mut test_stack,
+            &mut dict,
+        )
+        .unwrap();
+
+        assert_eq!(test_stack.data, &[0, 1, 0, 1, 0]);
+    }
+}
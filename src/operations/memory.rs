@@ -0,0 +1,295 @@
+use super::forth_operation::ForthOperation;
+use crate::{
+    cell::Cell,
+    data_memory::{BASE_ADDRESS, DataMemory, offset_address},
+    errors::ForthError,
+    forth_value::ForthValue,
+    stack::Stack,
+};
+
+/// Enum that represents the memory operations that can be interpreted by the program.
+///
+/// The different ones are:
+///
+/// - Fetch: replaces an address with the value of its cell ('@').
+/// - Store: stores a value in the cell of an address ('!').
+/// - AddStore: adds a value to the cell of an address ('+!').
+/// - Here: pushes the address of the next free cell.
+/// - Allot: reserves (or releases, if negative) an amount of cells.
+/// - Comma: reserves one cell and stores a value in it (',').
+/// - TwoFetch: replaces an address with the values of its cell and the next one, as a double-cell number ('2@').
+/// - TwoStore: stores a double-cell number in the cell of an address and the next one ('2!').
+/// - Base: pushes the address of the BASE variable, which contains the number base used to parse and print the numbers.
+/// - SetBase: tuple that contains the number base to store in BASE ('HEX', 'DECIMAL', 'OCTAL' and 'BINARY').
+///
+
+#[derive(Debug)]
+pub enum MemoryOperation {
+    Fetch,
+    Store,
+    AddStore,
+    Here,
+    Allot,
+    Comma,
+    TwoFetch,
+    TwoStore,
+    Base,
+    SetBase(Cell),
+}
+
+///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
+pub fn parse_memory(token: &str) -> Option<ForthValue> {
+    match token {
+        "@" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::Fetch,
+        ))),
+        "!" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::Store,
+        ))),
+        "+!" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::AddStore,
+        ))),
+        "HERE" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::Here,
+        ))),
+        "ALLOT" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::Allot,
+        ))),
+        "," => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::Comma,
+        ))),
+        "2@" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::TwoFetch,
+        ))),
+        "2!" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::TwoStore,
+        ))),
+        "BASE" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::Base,
+        ))),
+        "HEX" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::SetBase(16),
+        ))),
+        "DECIMAL" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::SetBase(10),
+        ))),
+        "OCTAL" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::SetBase(8),
+        ))),
+        "BINARY" => Some(ForthValue::Operation(ForthOperation::Memory(
+            MemoryOperation::SetBase(2),
+        ))),
+        _ => None,
+    }
+}
+
+///Function that executes a memory operation by receiving a reference to a memory operation, the stack reference as mutable so it can be updated with the result,
+/// and the data memory as mutable so its cells can be read and written.
+/// The sum of '+!' is fitted in a cell by the overflow policy of the stack.
+/// Returns the error of the values popped or pushed, the error of the memory access, or overflow.
+pub fn execute_memory_op(
+    op: &MemoryOperation,
+    stack: &mut Stack,
+    memory: &mut DataMemory,
+) -> Result<(), ForthError> {
+    match op {
+        MemoryOperation::Fetch => {
+            let address = stack.pop()?;
+            stack.push(memory.fetch(address)?)
+        }
+        MemoryOperation::Store => {
+            let address = stack.pop()?;
+            let value = stack.pop()?;
+            memory.store(address, value)
+        }
+        MemoryOperation::AddStore => {
+            let address = stack.pop()?;
+            let value = stack.pop()?;
+            let current = memory.fetch(address)?;
+            let result = stack.cell().fit(i128::from(current) + i128::from(value))?;
+            memory.store(address, result)
+        }
+        MemoryOperation::Here => stack.push(memory.here()),
+        MemoryOperation::Allot => {
+            let amount = stack.pop()?;
+            memory.allot(amount)
+        }
+        MemoryOperation::Comma => {
+            let value = stack.pop()?;
+            memory.comma(value).map(|_| ())
+        }
+        MemoryOperation::TwoFetch => {
+            let address = stack.pop()?;
+            let high = memory.fetch(address)?;
+            let low = memory.fetch(offset_address(address, 1)?)?;
+            stack.push(low)?;
+            stack.push(high)
+        }
+        MemoryOperation::TwoStore => {
+            let address = stack.pop()?;
+            let high = stack.pop()?;
+            let low = stack.pop()?;
+            let next = offset_address(address, 1)?;
+            memory.fetch(next)?;
+            memory.store(address, high)?;
+            memory.store(next, low)
+        }
+        MemoryOperation::Base => stack.push(BASE_ADDRESS),
+        MemoryOperation::SetBase(base) => memory.store(BASE_ADDRESS, *base),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MemoryOperation, execute_memory_op};
+    use crate::{
+        cell::{Cell, CellConfig, CellWidth},
+        data_memory::{BASE_ADDRESS, DataMemory, TRANSIENT_ADDRESS, TRANSIENT_SIZE},
+        errors::ForthError,
+        utils::init_stack,
+    };
+
+    #[test]
+    fn test_store_fetch() {
+        let mut memory = DataMemory::new(100);
+        memory.allot(2).unwrap();
+        let mut test_stack = init_stack(&[7, 1]);
+        execute_memory_op(&MemoryOperation::Store, &mut test_stack, &mut memory).unwrap();
+        test_stack.push(1).unwrap();
+        execute_memory_op(&MemoryOperation::Fetch, &mut test_stack, &mut memory).unwrap();
+        assert_eq!(test_stack.data, &[7]);
+    }
+
+    #[test]
+    fn test_add_store() {
+        let mut memory = DataMemory::new(100);
+        memory.comma(5).unwrap();
+        let mut test_stack = init_stack(&[3, 0]);
+        execute_memory_op(&MemoryOperation::AddStore, &mut test_stack, &mut memory).unwrap();
+        assert_eq!(memory.cells, &[8]);
+    }
+
+    #[test]
+    fn test_here_allot_comma() {
+        let mut memory = DataMemory::new(100);
+        let mut test_stack = init_stack(&[3]);
+        execute_memory_op(&MemoryOperation::Allot, &mut test_stack, &mut memory).unwrap();
+        test_stack.push(9).unwrap();
+        execute_memory_op(&MemoryOperation::Comma, &mut test_stack, &mut memory).unwrap();
+        execute_memory_op(&MemoryOperation::Here, &mut test_stack, &mut memory).unwrap();
+        assert_eq!(test_stack.data, &[4]);
+        assert_eq!(memory.cells, &[0, 0, 0, 9]);
+    }
+
+    #[test]
+    fn test_invalid_address() {
+        let mut memory = DataMemory::new(100);
+        memory.allot(1).unwrap();
+        for address in [-1, 1, 100] {
+            let mut test_stack = init_stack(&[address]);
+            assert_eq!(
+                execute_memory_op(&MemoryOperation::Fetch, &mut test_stack, &mut memory),
+                Err(ForthError::InvalidAddress)
+            );
+        }
+        let mut test_stack = init_stack(&[-2]);
+        assert_eq!(
+            execute_memory_op(&MemoryOperation::Allot, &mut test_stack, &mut memory),
+            Err(ForthError::InvalidAddress)
+        );
+    }
+
+    #[test]
+    fn test_memory_overflow() {
+        let mut memory = DataMemory::new(10);
+        let mut test_stack = init_stack(&[6]);
+        assert_eq!(
+            execute_memory_op(&MemoryOperation::Allot, &mut test_stack, &mut memory),
+            Err(ForthError::MemoryOverflow)
+        );
+        assert!(memory.cells.is_empty());
+
+        let cell = CellConfig {
+            width: CellWidth::Bits64,
+            ..Default::default()
+        };
+        let mut memory = DataMemory::with_cells(40, &cell);
+        let mut test_stack = init_stack(&[6]);
+        assert_eq!(
+            execute_memory_op(&MemoryOperation::Allot, &mut test_stack, &mut memory),
+            Err(ForthError::MemoryOverflow)
+        );
+        test_stack.push(5).unwrap();
+        execute_memory_op(&MemoryOperation::Allot, &mut test_stack, &mut memory).unwrap();
+    }
+
+    #[test]
+    fn test_transient_buffers() {
+        let mut memory = DataMemory::new(100);
+        let first = memory.store_transient("ab").unwrap();
+        let second = memory.store_transient("cd").unwrap();
+        assert_eq!(first, TRANSIENT_ADDRESS);
+        assert_eq!(second, TRANSIENT_ADDRESS + TRANSIENT_SIZE);
+        assert_eq!(memory.fetch_range(first, 2), Ok(&[97, 98][..]));
+        assert_eq!(memory.store_transient("ef"), Ok(first));
+        assert_eq!(memory.fetch_range(second, 2), Ok(&[99, 100][..]));
+        memory.store(second, 120).unwrap();
+        assert_eq!(memory.fetch(second), Ok(120));
+        assert!(memory.cells.is_empty());
+
+        let text = "a".repeat(TRANSIENT_SIZE as usize + 1);
+        assert_eq!(
+            memory.store_transient(&text),
+            Err(ForthError::MemoryOverflow)
+        );
+    }
+
+    #[test]
+    fn test_base() {
+        let mut memory = DataMemory::new(100);
+        let mut test_stack = init_stack(&[]);
+        execute_memory_op(&MemoryOperation::SetBase(16), &mut test_stack, &mut memory).unwrap();
+        execute_memory_op(&MemoryOperation::Base, &mut test_stack, &mut memory).unwrap();
+        execute_memory_op(&MemoryOperation::Fetch, &mut test_stack, &mut memory).unwrap();
+        assert_eq!(test_stack.data, &[16]);
+        assert_eq!(memory.base(), 16);
+        assert!(memory.cells.is_empty());
+
+        let mut test_stack = init_stack(&[1, BASE_ADDRESS]);
+        assert_eq!(
+            execute_memory_op(&MemoryOperation::Store, &mut test_stack, &mut memory),
+            Err(ForthError::InvalidBase)
+        );
+        assert_eq!(memory.base(), 16);
+    }
+
+    #[test]
+    fn test_two_store_two_fetch() {
+        let mut memory = DataMemory::new(100);
+        memory.allot(2).unwrap();
+        let mut test_stack = init_stack(&[5, 7, 0]);
+        execute_memory_op(&MemoryOperation::TwoStore, &mut test_stack, &mut memory).unwrap();
+        assert_eq!(memory.cells, &[7, 5]);
+        test_stack.push(0).unwrap();
+        execute_memory_op(&MemoryOperation::TwoFetch, &mut test_stack, &mut memory).unwrap();
+        assert_eq!(test_stack.data, &[5, 7]);
+
+        let mut test_stack = init_stack(&[5, 7, 1]);
+        assert_eq!(
+            execute_memory_op(&MemoryOperation::TwoStore, &mut test_stack, &mut memory),
+            Err(ForthError::InvalidAddress)
+        );
+        assert_eq!(memory.cells, &[7, 5]);
+
+        let mut test_stack = init_stack(&[1, 2, Cell::MAX]);
+        assert_eq!(
+            execute_memory_op(&MemoryOperation::TwoStore, &mut test_stack, &mut memory),
+            Err(ForthError::InvalidAddress)
+        );
+        let mut test_stack = init_stack(&[Cell::MAX]);
+        assert_eq!(
+            execute_memory_op(&MemoryOperation::TwoFetch, &mut test_stack, &mut memory),
+            Err(ForthError::InvalidAddress)
+        );
+    }
+}
@@ -1,6 +1,15 @@
 pub mod arithmetic;
 pub mod boolean;
 pub mod conditional;
+pub mod double;
+pub mod file;
+#[cfg(feature = "float")]
+pub mod float;
 pub mod forth_operation;
+pub mod input;
+pub mod loops;
+pub mod memory;
 pub mod output;
+pub mod return_stack_type;
 pub mod stack_type;
+pub mod string;
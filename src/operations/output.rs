@@ -1,5 +1,5 @@
 use super::forth_operation::ForthOperation;
-use crate::{errors::print_error, forth_value::ForthValue, stack::Stack};
+use crate::{errors::ForthError, forth_value::ForthValue, output_sink::OutputSink, stack::Stack};
 
 /// Enum that represents the output operations that can be interpreted by the program.
 ///
@@ -9,6 +9,8 @@ use crate::{errors::print_error, forth_value::ForthValue, stack::Stack};
 /// - Emit: express a number as an ascii char.
 /// - Cr: line break.
 /// - DotQuote: tuple that contains a String to print.
+/// - DoubleDot: pops a double-cell number and prints it ('D.').
+/// - DotS: prints the amount of values of the stack and the values, without removing them ('.S').
 ///
 
 #[derive(Debug)]
@@ -17,6 +19,8 @@ pub enum OutputOperation {
     Emit,
     Cr,
     DotQuote(String),
+    DotS,
+    DoubleDot,
 }
 
 ///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
@@ -31,150 +35,479 @@ pub fn parse_output(token: &str) -> Option<ForthValue> {
         "CR" => Some(ForthValue::Operation(ForthOperation::Output(
             OutputOperation::Cr,
         ))),
+        ".S" => Some(ForthValue::Operation(ForthOperation::Output(
+            OutputOperation::DotS,
+        ))),
+        "D." => Some(ForthValue::Operation(ForthOperation::Output(
+            OutputOperation::DoubleDot,
+        ))),
         _ => None, //DotQuote (para imprimir por pantalla) lo manejamos aparte
     }
 }
 
+/// Enum that represents how the output operations print their text.
+///
+/// The different ones are:
+///
+/// - Standard: as Forth-79 does, '.' prints the number followed by a space, 'EMIT' prints only the char, '."' prints only its text and 'CR' is the only line break.
+/// - Compatibility: as the first versions of the program did, every operation prints its text followed by a line break.
+/// - Separated: as the tests of the course (tests_catedra) specify, every operation prints its text followed by a space, and 'CR' is the only line break.
+///
+
+#[derive(Debug, Default, PartialEq)]
+pub enum OutputMode {
+    #[default]
+    Standard,
+    Compatibility,
+    Separated,
+}
+
 ///Function that executes an output operation by receiving a reference to an output operation and the stack reference as mutable so it can be updated with the result.
-pub fn execute_output_op(op: &OutputOperation, stack: &mut Stack) {
-    match op {
-        OutputOperation::Dot => match stack.pop() {
-            Ok(a) => println!("{a}"),
-            Err(e) => print_error(e),
-        },
-        OutputOperation::Cr => {
-            println!();
+/// The text is written to the output sink received by parameter, followed by the separator of the output mode, and the numbers are printed in the number base received by parameter.
+/// Returns stack-underflow if the value to print could not be popped, or the error of the output sink.
+pub fn execute_output_op(
+    op: &OutputOperation,
+    stack: &mut Stack,
+    output: &mut dyn OutputSink,
+    mode: &OutputMode,
+    base: u32,
+) -> Result<(), ForthError> {
+    let text = match op {
+        OutputOperation::Dot => format_number(i128::from(stack.pop()?), base),
+        OutputOperation::DoubleDot => format_number(stack.pop_double()?, base),
+        OutputOperation::Cr => return output.write_text("\n"),
+        OutputOperation::Emit => {
+            let ascii = stack.pop()? as u8;
+            (ascii as char).to_string()
         }
-        OutputOperation::Emit => match stack.pop() {
-            Ok(a) => {
-                let ascii = a as u8;
-                println!("{}", ascii as char);
-            }
-            Err(e) => print_error(e),
-        },
-        OutputOperation::DotQuote(text) => {
-            println!("{text}");
+        OutputOperation::DotQuote(text) => text.to_string(),
+        OutputOperation::DotS => format_stack(stack, base),
+    };
+    let separator = match op {
+        OutputOperation::Dot | OutputOperation::DoubleDot | OutputOperation::DotS => {
+            number_separator(mode)
+        }
+        _ => text_separator(mode),
+    };
+    output.write_text(&format!("{text}{separator}"))
+}
+
+/// Function that returns the separator the output mode received by parameter prints after a number ('.', 'D.', '.S' and, with the "float" feature, 'F.').
+pub fn number_separator(mode: &OutputMode) -> &'static str {
+    match mode {
+        OutputMode::Compatibility => "\n",
+        OutputMode::Standard | OutputMode::Separated => " ",
+    }
+}
+
+/// Function that returns the separator the output mode received by parameter prints after a text that is not a number ('EMIT', '."' and 'TYPE').
+pub fn text_separator(mode: &OutputMode) -> &'static str {
+    match mode {
+        OutputMode::Standard => "",
+        OutputMode::Compatibility => "\n",
+        OutputMode::Separated => " ",
+    }
+}
+
+/// Function that returns the text printed by '.S': the amount of values of the Stack received by parameter between '<' and '>', followed by its values from the bottom to the top
+/// in the number base received by parameter.
+pub fn format_stack(stack: &Stack, base: u32) -> String {
+    let mut text = format!("<{}>", stack.data.len());
+    for &value in &stack.data {
+        text.push_str(&format!(" {}", format_number(i128::from(value), base)));
+    }
+    text
+}
+
+/// Function that returns the text of the number received by parameter in the number base received by parameter (with uppercase digits, and a '-' if it is negative).
+pub fn format_number(value: i128, base: u32) -> String {
+    let mut magnitude = value.unsigned_abs();
+    let mut digits = Vec::new();
+    loop {
+        let digit = (magnitude % u128::from(base)) as u32;
+        digits.push(
+            char::from_digit(digit, base)
+                .unwrap_or('?')
+                .to_ascii_uppercase(),
+        );
+        magnitude /= u128::from(base);
+        if magnitude == 0 {
+            break;
         }
     }
+    if value < 0 {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
 }
 
 #[cfg(test)]
-//observacion: sobre estos no se testeo explícitamente que la salida sea la misma, dado que esto se ve reflejado al ejecutar el test
 mod tests {
-    use super::{OutputOperation, execute_output_op};
-    use crate::utils::init_stack;
+    use super::{OutputMode, OutputOperation, execute_output_op, format_number, format_stack};
+    use crate::{output_sink::BufferOutput, utils::init_stack};
     #[test]
     fn test_dot_without_leftover() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[1, 2]);
-        execute_output_op(&OutputOperation::Dot, &mut test_stack);
-        execute_output_op(&OutputOperation::Dot, &mut test_stack);
+        execute_output_op(
+            &OutputOperation::Dot,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Dot,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "2\n1\n");
     }
 
     #[test]
     fn test_dot_with_leftover() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[1, 2, 3, 4, 5]);
-        execute_output_op(&OutputOperation::Dot, &mut test_stack);
-        execute_output_op(&OutputOperation::Dot, &mut test_stack);
-        execute_output_op(&OutputOperation::Dot, &mut test_stack);
+        execute_output_op(
+            &OutputOperation::Dot,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Dot,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Dot,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[1, 2]);
+        assert_eq!(output.text, "5\n4\n3\n");
     }
 
     #[test]
     fn test_cr_1() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[]);
-        execute_output_op(&OutputOperation::Cr, &mut test_stack);
+        execute_output_op(
+            &OutputOperation::Cr,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "\n");
     }
 
     #[test]
     fn test_cr_2() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[]);
-        execute_output_op(&OutputOperation::Cr, &mut test_stack);
-        execute_output_op(&OutputOperation::Cr, &mut test_stack);
+        execute_output_op(
+            &OutputOperation::Cr,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Cr,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "\n\n");
     }
 
     #[test]
     fn test_dot_and_cr() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[1, 2]);
-        execute_output_op(&OutputOperation::Dot, &mut test_stack);
-        execute_output_op(&OutputOperation::Cr, &mut test_stack);
-        execute_output_op(&OutputOperation::Cr, &mut test_stack);
-        execute_output_op(&OutputOperation::Dot, &mut test_stack);
+        execute_output_op(
+            &OutputOperation::Dot,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Cr,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Cr,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Dot,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "2\n\n\n1\n");
     }
 
     #[test]
     fn test_emit_uppercase() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[65]);
-        execute_output_op(&OutputOperation::Emit, &mut test_stack);
+        execute_output_op(
+            &OutputOperation::Emit,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "A\n");
     }
 
     #[test]
     fn test_emit_lowercase() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[97]);
-        execute_output_op(&OutputOperation::Emit, &mut test_stack);
+        execute_output_op(
+            &OutputOperation::Emit,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "a\n");
     }
 
     #[test]
     fn test_emit_multiple() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[68, 67, 66, 65]);
-        execute_output_op(&OutputOperation::Emit, &mut test_stack);
-        execute_output_op(&OutputOperation::Emit, &mut test_stack);
-        execute_output_op(&OutputOperation::Emit, &mut test_stack);
-        execute_output_op(&OutputOperation::Emit, &mut test_stack);
+        execute_output_op(
+            &OutputOperation::Emit,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Emit,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Emit,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Emit,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "A\nB\nC\nD\n");
     }
 
     #[test]
     fn test_dot_quote_hello_world() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[]);
         execute_output_op(
             &OutputOperation::DotQuote("hello world".to_string()),
             &mut test_stack,
-        );
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "hello world\n");
     }
 
     #[test]
     fn test_dot_quote_multiple_whitespace() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[]);
         execute_output_op(
             &OutputOperation::DotQuote("hello      world!".to_string()),
             &mut test_stack,
-        );
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "hello      world!\n");
     }
 
     #[test]
     fn test_dot_quote_multiples() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[]);
         execute_output_op(
             &OutputOperation::DotQuote("hello".to_string()),
             &mut test_stack,
-        );
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         execute_output_op(
             &OutputOperation::DotQuote("world".to_string()),
             &mut test_stack,
-        );
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "hello\nworld\n");
     }
 
     #[test]
     fn test_dot_quote_and_cr() {
+        let mut output = BufferOutput::new();
         let mut test_stack = init_stack(&[]);
         execute_output_op(
             &OutputOperation::DotQuote("hello".to_string()),
             &mut test_stack,
-        );
-        execute_output_op(&OutputOperation::Cr, &mut test_stack);
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        execute_output_op(
+            &OutputOperation::Cr,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
         execute_output_op(
             &OutputOperation::DotQuote("world".to_string()),
             &mut test_stack,
-        );
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        assert_eq!(test_stack.data, &[]);
+        assert_eq!(output.text, "hello\n\nworld\n");
+    }
+
+    #[test]
+    fn test_dot_s_keeps_stack() {
+        let mut output = BufferOutput::new();
+        let mut test_stack = init_stack(&[1, -2, 3]);
+        assert_eq!(format_stack(&test_stack, 10), "<3> 1 -2 3");
+        execute_output_op(
+            &OutputOperation::DotS,
+            &mut test_stack,
+            &mut output,
+            &OutputMode::Compatibility,
+            10,
+        )
+        .unwrap();
+        assert_eq!(test_stack.data, &[1, -2, 3]);
+        assert_eq!(format_stack(&init_stack(&[]), 10), "<0>");
+        assert_eq!(output.text, "<3> 1 -2 3\n");
+    }
+
+    #[test]
+    fn test_separated_mode() {
+        let mut output = BufferOutput::new();
+        let mut test_stack = init_stack(&[66, 65, 1]);
+        for op in [
+            OutputOperation::Dot,
+            OutputOperation::Emit,
+            OutputOperation::Cr,
+            OutputOperation::DotQuote("hi".to_string()),
+            OutputOperation::Emit,
+        ] {
+            execute_output_op(
+                &op,
+                &mut test_stack,
+                &mut output,
+                &OutputMode::Separated,
+                10,
+            )
+            .unwrap();
+        }
+        assert_eq!(output.text, "1 A \nhi B ");
+    }
+
+    #[test]
+    fn test_standard_mode() {
+        let mut output = BufferOutput::new();
+        let mut test_stack = init_stack(&[65, 1, 2, 3]);
+        for op in [
+            OutputOperation::Dot,
+            OutputOperation::Dot,
+            OutputOperation::DotS,
+            OutputOperation::Cr,
+            OutputOperation::DotQuote("is ".to_string()),
+            OutputOperation::Dot,
+            OutputOperation::Emit,
+        ] {
+            execute_output_op(&op, &mut test_stack, &mut output, &OutputMode::Standard, 10)
+                .unwrap();
+        }
+        assert_eq!(output.text, "3 2 <2> 65 1 \nis 1 A");
         assert_eq!(test_stack.data, &[]);
     }
+
+    #[test]
+    fn test_format_number_in_base() {
+        assert_eq!(format_number(255, 16), "FF");
+        assert_eq!(format_number(-10, 2), "-1010");
+        assert_eq!(format_number(0, 8), "0");
+        assert_eq!(format_number(i128::from(i64::MIN), 16), "-8000000000000000");
+        assert_eq!(format_stack(&init_stack(&[10, -1]), 16), "<2> A -1");
+    }
 }
@@ -0,0 +1,115 @@
+use super::forth_operation::ForthOperation;
+use crate::{
+    errors::ForthError,
+    forth_value::ForthValue,
+    return_stack::{ReturnFrame, ReturnStack},
+    stack::Stack,
+};
+
+/// Enum that represents the return stack operations that can be interpreted by the program.
+///
+/// The different ones are:
+///
+/// - ToReturn: moves the value on top of the stack to the return stack ('>R').
+/// - FromReturn: moves the value on top of the return stack to the stack ('R>').
+/// - FetchReturn: copies the value on top of the return stack to the stack ('R@').
+///
+
+#[derive(Debug)]
+pub enum ReturnStackOperation {
+    ToReturn,
+    FromReturn,
+    FetchReturn,
+}
+
+///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
+pub fn parse_return_stack_op(token: &str) -> Option<ForthValue> {
+    match token {
+        ">R" => Some(ForthValue::Operation(ForthOperation::ReturnStackOp(
+            ReturnStackOperation::ToReturn,
+        ))),
+        "R>" => Some(ForthValue::Operation(ForthOperation::ReturnStackOp(
+            ReturnStackOperation::FromReturn,
+        ))),
+        "R@" => Some(ForthValue::Operation(ForthOperation::ReturnStackOp(
+            ReturnStackOperation::FetchReturn,
+        ))),
+        _ => None,
+    }
+}
+
+///Function that executes a return stack operation by receiving a reference to a return stack operation, the stack reference as mutable so it can be updated with the result,
+/// and the return stack as mutable so the values can be moved between both.
+/// Returns the error of the value that could not be popped or pushed in any of them.
+pub fn execute_return_stack_op(
+    op: &ReturnStackOperation,
+    stack: &mut Stack,
+    return_stack: &mut ReturnStack,
+) -> Result<(), ForthError> {
+    match op {
+        ReturnStackOperation::ToReturn => {
+            let value = stack.pop()?;
+            return_stack.push(ReturnFrame::Value(value))
+        }
+        ReturnStackOperation::FromReturn => {
+            let value = return_stack.pop_value()?;
+            stack.push(value)
+        }
+        ReturnStackOperation::FetchReturn => stack.push(return_stack.peek_value()?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReturnStackOperation, execute_return_stack_op};
+    use crate::{errors::ForthError, return_stack::ReturnStack, utils::init_stack};
+
+    #[test]
+    fn test_move_values() {
+        let mut return_stack = ReturnStack::new(100, 10);
+        let mut test_stack = init_stack(&[1, 2]);
+        execute_return_stack_op(
+            &ReturnStackOperation::ToReturn,
+            &mut test_stack,
+            &mut return_stack,
+        )
+        .unwrap();
+        execute_return_stack_op(
+            &ReturnStackOperation::FetchReturn,
+            &mut test_stack,
+            &mut return_stack,
+        )
+        .unwrap();
+        execute_return_stack_op(
+            &ReturnStackOperation::FromReturn,
+            &mut test_stack,
+            &mut return_stack,
+        )
+        .unwrap();
+        assert_eq!(test_stack.data, &[1, 2, 2]);
+        assert!(return_stack.is_empty());
+    }
+
+    #[test]
+    fn test_return_stack_underflow() {
+        let mut return_stack = ReturnStack::new(100, 10);
+        let mut test_stack = init_stack(&[]);
+        for op in [
+            ReturnStackOperation::FromReturn,
+            ReturnStackOperation::FetchReturn,
+        ] {
+            assert_eq!(
+                execute_return_stack_op(&op, &mut test_stack, &mut return_stack),
+                Err(ForthError::ReturnStackUnderflow)
+            );
+        }
+        assert_eq!(
+            execute_return_stack_op(
+                &ReturnStackOperation::ToReturn,
+                &mut test_stack,
+                &mut return_stack
+            ),
+            Err(ForthError::StackUnderflow)
+        );
+    }
+}
@@ -1,250 +1,369 @@
 use super::forth_operation::ForthOperation;
-use crate::{errors::print_error, forth_value::ForthValue, stack::Stack};
+use crate::{cell::Cell, errors::ForthError, forth_value::ForthValue, stack::Stack};
 
 /// Enum that represents the stack_type operations that can be interpreted by the program.
 ///
 /// The different ones are:
 ///
 /// - Duplicate: duplicate a certain value.
+/// - DuplicateIfNonZero: duplicate the last value only if it is not zero ('?DUP').
 /// - Drop: removes a certain value.
 /// - Swap: change positions between the last two elements
 /// - Over: copy the first of the last two elements (penultimate) to the last position
 /// - Rotate: rotate values to the left
+/// - Pick: copy the n-th value (not counting n, as in Forth-79: 1 PICK is DUP and 2 PICK is OVER) to the last position.
+/// - Roll: move the n-th value (not counting n, as in Forth-79: 2 ROLL is SWAP and 3 ROLL is ROT) to the last position.
+/// - Depth: push the amount of values the stack had before it.
+/// - TwoDuplicate: duplicate the last pair of values.
+/// - TwoDrop: removes the last pair of values.
+/// - TwoSwap: change positions between the last two pairs of values.
+/// - TwoOver: copy the first of the last two pairs of values to the last position.
+/// - Nip: removes the penultimate value.
+/// - Tuck: copy the last value below the penultimate one.
 
 #[derive(Debug)]
 pub enum StackOperation {
     Duplicate,
+    DuplicateIfNonZero,
     Drop,
     Swap,
     Over,
     Rotate,
+    Pick,
+    Roll,
+    Depth,
+    TwoDuplicate,
+    TwoDrop,
+    TwoSwap,
+    TwoOver,
+    Nip,
+    Tuck,
 }
 
 ///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
 pub fn parse_stack_op(token: &str) -> Option<ForthValue> {
-    match token {
-        "DUP" => Some(ForthValue::Operation(ForthOperation::StackTypeOp(
-            StackOperation::Duplicate,
-        ))),
-        "DROP" => Some(ForthValue::Operation(ForthOperation::StackTypeOp(
-            StackOperation::Drop,
-        ))),
-        "SWAP" => Some(ForthValue::Operation(ForthOperation::StackTypeOp(
-            StackOperation::Swap,
-        ))),
-        "OVER" => Some(ForthValue::Operation(ForthOperation::StackTypeOp(
-            StackOperation::Over,
-        ))),
-        "ROT" => Some(ForthValue::Operation(ForthOperation::StackTypeOp(
-            StackOperation::Rotate,
-        ))),
-        _ => None,
-    }
+    let op = match token {
+        "DUP" => StackOperation::Duplicate,
+        "?DUP" => StackOperation::DuplicateIfNonZero,
+        "DROP" => StackOperation::Drop,
+        "SWAP" => StackOperation::Swap,
+        "OVER" => StackOperation::Over,
+        "ROT" => StackOperation::Rotate,
+        "PICK" => StackOperation::Pick,
+        "ROLL" => StackOperation::Roll,
+        "DEPTH" => StackOperation::Depth,
+        "2DUP" => StackOperation::TwoDuplicate,
+        "2DROP" => StackOperation::TwoDrop,
+        "2SWAP" => StackOperation::TwoSwap,
+        "2OVER" => StackOperation::TwoOver,
+        "NIP" => StackOperation::Nip,
+        "TUCK" => StackOperation::Tuck,
+        _ => return None,
+    };
+    Some(ForthValue::Operation(ForthOperation::StackTypeOp(op)))
 }
 
 ///Function that executes a stack_type operation by receiving a reference to a stack_type operation and the stack reference as mutable so it can be updated with the result.
-pub fn execute_stack_op(op: &StackOperation, stack: &mut Stack) {
+/// The operations that work on several values check the depth of the stack before changing it, so they leave it untouched when there are not enough values.
+/// Returns the error of the first value that could not be popped or pushed, or stack-underflow if the stack has not the values the operation needs.
+pub fn execute_stack_op(op: &StackOperation, stack: &mut Stack) -> Result<(), ForthError> {
     match op {
         StackOperation::Duplicate => handle_duplicate(stack),
-        StackOperation::Drop => handle_drop(stack),
+        StackOperation::DuplicateIfNonZero => match *stack.peek()? {
+            0 => Ok(()),
+            _ => handle_duplicate(stack),
+        },
+        StackOperation::Drop => stack.pop().map(|_| ()),
         StackOperation::Swap => handle_swap(stack),
         StackOperation::Over => handle_over(stack),
         StackOperation::Rotate => handle_rotate(stack),
+        StackOperation::Pick => handle_pick(stack),
+        StackOperation::Roll => handle_roll(stack),
+        StackOperation::Depth => stack.push(stack.data.len() as Cell),
+        StackOperation::TwoDuplicate => handle_copy_pair(stack, 2),
+        StackOperation::TwoDrop => {
+            let position = nth_position(stack, 2)?;
+            stack.data.truncate(position);
+            Ok(())
+        }
+        StackOperation::TwoSwap => {
+            let position = nth_position(stack, 4)?;
+            stack.data[position..].rotate_left(2);
+            Ok(())
+        }
+        StackOperation::TwoOver => handle_copy_pair(stack, 4),
+        StackOperation::Nip => {
+            let position = nth_position(stack, 2)?;
+            stack.data.remove(position);
+            Ok(())
+        }
+        StackOperation::Tuck => {
+            let position = nth_position(stack, 2)?;
+            let a = *stack.peek()?;
+            stack.push(a)?;
+            stack.data[position..].rotate_right(1);
+            Ok(())
+        }
     }
 }
 
-fn handle_duplicate(stack: &mut Stack) {
-    match stack.peek() {
-        Ok(a) => {
-            if let Err(e) = stack.push(*a) {
-                print_error(e);
-            }
-        }
-        Err(e) => print_error(e),
+/// Function that returns the position (in the data of the stack received by parameter) of its n-th value counting from the top, where 1 is the last one.
+/// Returns stack-underflow if n is less than 1 or the stack has less than n values.
+fn nth_position(stack: &Stack, n: Cell) -> Result<usize, ForthError> {
+    let depth = stack.data.len();
+    match usize::try_from(n) {
+        Ok(n) if (1..=depth).contains(&n) => Ok(depth - n),
+        _ => Err(ForthError::StackUnderflow),
     }
 }
 
-fn handle_drop(stack: &mut Stack) {
-    if let Err(e) = stack.pop() {
-        print_error(e);
-    }
+fn handle_duplicate(stack: &mut Stack) -> Result<(), ForthError> {
+    let a = *stack.peek()?;
+    stack.push(a)
 }
 
-fn handle_swap(stack: &mut Stack) {
-    let a = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
-        }
-    };
-    let b = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
-        }
-    };
-    if let Err(e) = stack.push(a) {
-        print_error(e);
-        return;
-    }
-    if let Err(e) = stack.push(b) {
-        print_error(e);
-    }
+fn handle_swap(stack: &mut Stack) -> Result<(), ForthError> {
+    let a = stack.pop()?;
+    let b = stack.pop()?;
+    stack.push(a)?;
+    stack.push(b)
 }
 
-fn handle_over(stack: &mut Stack) {
-    let a = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
-        }
-    };
-    let b = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
-        }
-    };
-    if let Err(e) = stack.push(b) {
-        print_error(e);
-        return;
-    }
-    if let Err(e) = stack.push(a) {
-        print_error(e);
-        return;
-    }
-    if let Err(e) = stack.push(b) {
-        print_error(e);
-    }
+fn handle_over(stack: &mut Stack) -> Result<(), ForthError> {
+    let a = stack.pop()?;
+    let b = stack.pop()?;
+    stack.push(b)?;
+    stack.push(a)?;
+    stack.push(b)
 }
 
-fn handle_rotate(stack: &mut Stack) {
-    let a = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
-        }
-    };
-    let b = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
-        }
-    };
-    let c = match stack.pop() {
-        Ok(val) => val,
-        Err(e) => {
-            print_error(e);
-            return;
-        }
-    };
-    if let Err(e) = stack.push(b) {
-        print_error(e);
-        return;
-    }
-    if let Err(e) = stack.push(a) {
-        print_error(e);
-        return;
-    }
-    if let Err(e) = stack.push(c) {
-        print_error(e);
-    }
+fn handle_rotate(stack: &mut Stack) -> Result<(), ForthError> {
+    let a = stack.pop()?;
+    let b = stack.pop()?;
+    let c = stack.pop()?;
+    stack.push(b)?;
+    stack.push(a)?;
+    stack.push(c)
+}
+
+fn handle_pick(stack: &mut Stack) -> Result<(), ForthError> {
+    let n = stack.pop()?;
+    let position = nth_position(stack, n)?;
+    stack.push(stack.data[position])
+}
+
+fn handle_roll(stack: &mut Stack) -> Result<(), ForthError> {
+    let n = stack.pop()?;
+    let position = nth_position(stack, n)?;
+    let value = stack.data.remove(position);
+    stack.push(value)
+}
+
+/// Function that copies the pair of values that starts at the n-th value (counting from the top) of the stack received by parameter to the last position ('2DUP' and '2OVER').
+fn handle_copy_pair(stack: &mut Stack, n: Cell) -> Result<(), ForthError> {
+    let position = nth_position(stack, n)?;
+    let (a, b) = (stack.data[position], stack.data[position + 1]);
+    stack.push(a)?;
+    stack.push(b)
 }
 
 #[cfg(test)]
 mod tests {
     use super::{StackOperation, execute_stack_op};
-    use crate::utils::init_stack;
+    use crate::{errors::ForthError, utils::init_stack};
 
     #[test]
     fn test_dup_1() {
         let mut test_stack = init_stack(&[1]);
-        execute_stack_op(&StackOperation::Duplicate, &mut test_stack);
+        execute_stack_op(&StackOperation::Duplicate, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 1]);
     }
 
     #[test]
     fn test_dup_2() {
         let mut test_stack = init_stack(&[1, 2]);
-        execute_stack_op(&StackOperation::Duplicate, &mut test_stack);
+        execute_stack_op(&StackOperation::Duplicate, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 2, 2]);
     }
 
     #[test]
     fn test_drop_1() {
         let mut test_stack = init_stack(&[1]);
-        execute_stack_op(&StackOperation::Drop, &mut test_stack);
+        execute_stack_op(&StackOperation::Drop, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[]);
     }
 
     #[test]
     fn test_drop_2() {
         let mut test_stack = init_stack(&[1, 2]);
-        execute_stack_op(&StackOperation::Drop, &mut test_stack);
+        execute_stack_op(&StackOperation::Drop, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1]);
     }
 
     #[test]
     fn test_swap_1() {
         let mut test_stack = init_stack(&[1, 2]);
-        execute_stack_op(&StackOperation::Swap, &mut test_stack);
+        execute_stack_op(&StackOperation::Swap, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[2, 1]);
     }
 
     #[test]
     fn test_swap_2() {
         let mut test_stack = init_stack(&[1, 2, 3]);
-        execute_stack_op(&StackOperation::Swap, &mut test_stack);
+        execute_stack_op(&StackOperation::Swap, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 3, 2]);
     }
 
     #[test]
     fn test_over_1() {
         let mut test_stack = init_stack(&[1, 2]);
-        execute_stack_op(&StackOperation::Over, &mut test_stack);
+        execute_stack_op(&StackOperation::Over, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 2, 1]);
     }
 
     #[test]
     fn test_over_2() {
         let mut test_stack = init_stack(&[1, 2, 3]);
-        execute_stack_op(&StackOperation::Over, &mut test_stack);
+        execute_stack_op(&StackOperation::Over, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 2, 3, 2]);
     }
 
     #[test]
     fn test_rot_1() {
         let mut test_stack = init_stack(&[1, 2, 3]);
-        execute_stack_op(&StackOperation::Rotate, &mut test_stack);
+        execute_stack_op(&StackOperation::Rotate, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[2, 3, 1]);
     }
 
     #[test]
     fn test_rot_2() {
         let mut test_stack = init_stack(&[1, 2, 3]);
-        execute_stack_op(&StackOperation::Rotate, &mut test_stack);
-        execute_stack_op(&StackOperation::Rotate, &mut test_stack);
-        execute_stack_op(&StackOperation::Rotate, &mut test_stack);
+        execute_stack_op(&StackOperation::Rotate, &mut test_stack).unwrap();
+        execute_stack_op(&StackOperation::Rotate, &mut test_stack).unwrap();
+        execute_stack_op(&StackOperation::Rotate, &mut test_stack).unwrap();
         assert_eq!(test_stack.data, &[1, 2, 3]);
     }
 
     #[test]
     fn test_drop_empty_stack() {
         let mut test_stack = init_stack(&[]);
-        execute_stack_op(&StackOperation::Drop, &mut test_stack);
+        assert_eq!(
+            execute_stack_op(&StackOperation::Drop, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
         assert_eq!(test_stack.data, &[]);
     }
 
     #[test]
     fn test_dup_empty_stack() {
         let mut test_stack = init_stack(&[]);
-        execute_stack_op(&StackOperation::Duplicate, &mut test_stack);
+        assert_eq!(
+            execute_stack_op(&StackOperation::Duplicate, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
         assert_eq!(test_stack.data, &[]);
     }
+
+    #[test]
+    fn test_question_dup() {
+        let mut test_stack = init_stack(&[0]);
+        execute_stack_op(&StackOperation::DuplicateIfNonZero, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[0]);
+
+        let mut test_stack = init_stack(&[3]);
+        execute_stack_op(&StackOperation::DuplicateIfNonZero, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[3, 3]);
+    }
+
+    #[test]
+    fn test_pick() {
+        let mut test_stack = init_stack(&[10, 20, 30, 3]);
+        execute_stack_op(&StackOperation::Pick, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[10, 20, 30, 10]);
+
+        let mut test_stack = init_stack(&[10, 20, 1]);
+        execute_stack_op(&StackOperation::Pick, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[10, 20, 20]);
+    }
+
+    #[test]
+    fn test_pick_out_of_range() {
+        let mut test_stack = init_stack(&[10, 20, 3]);
+        assert_eq!(
+            execute_stack_op(&StackOperation::Pick, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
+        assert_eq!(test_stack.data, &[10, 20]);
+
+        let mut test_stack = init_stack(&[10, 0]);
+        assert_eq!(
+            execute_stack_op(&StackOperation::Pick, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
+
+        let mut test_stack = init_stack(&[10, -1]);
+        assert_eq!(
+            execute_stack_op(&StackOperation::Roll, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
+    }
+
+    #[test]
+    fn test_roll() {
+        let mut test_stack = init_stack(&[1, 2, 3, 4, 4]);
+        execute_stack_op(&StackOperation::Roll, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[2, 3, 4, 1]);
+
+        let mut test_stack = init_stack(&[1, 2, 3, 3]);
+        execute_stack_op(&StackOperation::Roll, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[2, 3, 1]);
+    }
+
+    #[test]
+    fn test_depth() {
+        let mut test_stack = init_stack(&[]);
+        execute_stack_op(&StackOperation::Depth, &mut test_stack).unwrap();
+        execute_stack_op(&StackOperation::Depth, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[0, 1]);
+    }
+
+    #[test]
+    fn test_pair_operations() {
+        let mut test_stack = init_stack(&[1, 2]);
+        execute_stack_op(&StackOperation::TwoDuplicate, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[1, 2, 1, 2]);
+
+        let mut test_stack = init_stack(&[1, 2, 3, 4]);
+        execute_stack_op(&StackOperation::TwoSwap, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[3, 4, 1, 2]);
+        execute_stack_op(&StackOperation::TwoOver, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[3, 4, 1, 2, 3, 4]);
+        execute_stack_op(&StackOperation::TwoDrop, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[3, 4, 1, 2]);
+
+        let mut test_stack = init_stack(&[1, 2, 3]);
+        assert_eq!(
+            execute_stack_op(&StackOperation::TwoSwap, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
+        assert_eq!(test_stack.data, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_nip_and_tuck() {
+        let mut test_stack = init_stack(&[1, 2]);
+        execute_stack_op(&StackOperation::Nip, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[2]);
+
+        let mut test_stack = init_stack(&[1, 2]);
+        execute_stack_op(&StackOperation::Tuck, &mut test_stack).unwrap();
+        assert_eq!(test_stack.data, &[2, 1, 2]);
+
+        let mut test_stack = init_stack(&[1]);
+        assert_eq!(
+            execute_stack_op(&StackOperation::Nip, &mut test_stack),
+            Err(ForthError::StackUnderflow)
+        );
+        assert_eq!(test_stack.data, &[1]);
+    }
 }
@@ -0,0 +1,213 @@
+use super::{forth_operation::ForthOperation, output::text_separator};
+use crate::{
+    cell::Cell, context::ForthContext, data_memory::offset_address, errors::ForthError,
+    forth_value::ForthValue, stack::Stack,
+};
+use std::cmp::Ordering;
+
+/// Enum that represents the string operations that can be interpreted by the program.
+/// A string is kept in the data memory with one char code in each cell, and it is referred by the address of its first cell and its length (as 'S"' pushes them).
+///
+/// The different ones are:
+///
+/// - Type: prints a string ('TYPE').
+/// - Count: replaces the address of a counted string (whose first cell is its length) with the address of its text and its length ('COUNT').
+/// - CMove: copies an amount of cells from an address to another one, from the first cell to the last one ('CMOVE').
+/// - Compare: compares two strings, pushing 0 if they are equal, -1 if the first one is less than the second one, or 1 otherwise ('COMPARE').
+/// - Search: searches a string in another one, pushing the rest of the latter from the first match and true, or the whole latter and false if there is no match ('SEARCH').
+///
+
+#[derive(Debug)]
+pub enum StringOperation {
+    Type,
+    Count,
+    CMove,
+    Compare,
+    Search,
+}
+
+///Function which converts a token received by parameter as &str to a ForthValue if exists, or None if not.
+pub fn parse_string_op(token: &str) -> Option<ForthValue> {
+    let op = match token {
+        "TYPE" => StringOperation::Type,
+        "COUNT" => StringOperation::Count,
+        "CMOVE" => StringOperation::CMove,
+        "COMPARE" => StringOperation::Compare,
+        "SEARCH" => StringOperation::Search,
+        _ => return None,
+    };
+    Some(ForthValue::Operation(ForthOperation::String(op)))
+}
+
+///Function that executes a string operation by receiving a reference to a string operation, the stack reference as mutable so it can be updated with the result,
+/// and the context, whose data memory keeps the strings and whose output sink receives the text printed by 'TYPE'.
+/// Returns the error of the first value that could not be popped or pushed, invalid-address if a string is not in the allocated cells, or the error of the output sink.
+pub fn execute_string_op(
+    op: &StringOperation,
+    stack: &mut Stack,
+    context: &mut ForthContext,
+) -> Result<(), ForthError> {
+    match op {
+        StringOperation::Type => {
+            let length = stack.pop()?;
+            let address = stack.pop()?;
+            let text = cells_to_string(context.memory.fetch_range(address, length)?);
+            let separator = text_separator(&context.output_mode);
+            context.output.write_text(&format!("{text}{separator}"))
+        }
+        StringOperation::Count => {
+            let address = stack.pop()?;
+            let length = context.memory.fetch(address)?;
+            stack.push(offset_address(address, 1)?)?;
+            stack.push(length)
+        }
+        StringOperation::CMove => {
+            let amount = stack.pop()?;
+            let destination = stack.pop()?;
+            let source = stack.pop()?;
+            for offset in 0..amount.max(0) {
+                let value = context.memory.fetch(offset_address(source, offset)?)?;
+                context
+                    .memory
+                    .store(offset_address(destination, offset)?, value)?;
+            }
+            Ok(())
+        }
+        StringOperation::Compare => {
+            let (first, second) = pop_two_strings(stack)?;
+            let first = context.memory.fetch_range(first.0, first.1)?;
+            let second = context.memory.fetch_range(second.0, second.1)?;
+            stack.push(match first.cmp(second) {
+                Ordering::Less => -1,
+                Ordering::Equal => 0,
+                Ordering::Greater => 1,
+            })
+        }
+        StringOperation::Search => {
+            let ((address, length), needle) = pop_two_strings(stack)?;
+            let haystack = context.memory.fetch_range(address, length)?;
+            let needle = context.memory.fetch_range(needle.0, needle.1)?;
+            let found = (0..=haystack.len().saturating_sub(needle.len()))
+                .find(|&offset| haystack[offset..].starts_with(needle));
+            match found {
+                Some(offset) => {
+                    stack.push(address + offset as Cell)?;
+                    stack.push(length - offset as Cell)?;
+                    stack.push(-1)
+                }
+                None => {
+                    stack.push(address)?;
+                    stack.push(length)?;
+                    stack.push(0)
+                }
+            }
+        }
+    }
+}
+
+/// A string as its address and its length.
+type StringRef = (Cell, Cell);
+
+/// Function that pops two strings from the Stack received by parameter, returning them in the order they were pushed.
+fn pop_two_strings(stack: &mut Stack) -> Result<(StringRef, StringRef), ForthError> {
+    let second_length = stack.pop()?;
+    let second_address = stack.pop()?;
+    let first_length = stack.pop()?;
+    let first_address = stack.pop()?;
+    Ok((
+        (first_address, first_length),
+        (second_address, second_length),
+    ))
+}
+
+/// Function that converts the char codes received by parameter to the text they represent (as 'EMIT' does with each one).
+pub fn cells_to_string(cells: &[Cell]) -> String {
+    cells.iter().map(|&code| code as u8 as char).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StringOperation, execute_string_op};
+    use crate::{
+        cell::Cell, context::ForthContext, errors::ForthError, output_sink::BufferOutput,
+        utils::init_stack,
+    };
+
+    fn string_context(texts: &[&str]) -> ForthContext {
+        let mut context = ForthContext {
+            output: Box::new(BufferOutput::new()),
+            ..Default::default()
+        };
+        for text in texts {
+            context.memory.store_string(text).unwrap();
+        }
+        context
+    }
+
+    #[test]
+    fn test_type_and_count() {
+        let mut context = string_context(&["\u{5}hello"]);
+        let mut test_stack = init_stack(&[0]);
+        execute_string_op(&StringOperation::Count, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[1, 5]);
+        execute_string_op(&StringOperation::Type, &mut test_stack, &mut context).unwrap();
+        assert_eq!(context.output.contents(), Some("hello"));
+
+        let mut test_stack = init_stack(&[4, 5]);
+        assert_eq!(
+            execute_string_op(&StringOperation::Type, &mut test_stack, &mut context),
+            Err(ForthError::InvalidAddress)
+        );
+        let mut test_stack = init_stack(&[Cell::MAX]);
+        assert_eq!(
+            execute_string_op(&StringOperation::Count, &mut test_stack, &mut context),
+            Err(ForthError::InvalidAddress)
+        );
+    }
+
+    #[test]
+    fn test_cmove() {
+        let mut context = string_context(&["abc", "xyz"]);
+        let mut test_stack = init_stack(&[0, 3, 2]);
+        execute_string_op(&StringOperation::CMove, &mut test_stack, &mut context).unwrap();
+        assert_eq!(context.memory.cells, &[97, 98, 99, 97, 98, 122]);
+
+        let mut test_stack = init_stack(&[0, Cell::MAX, 2]);
+        assert_eq!(
+            execute_string_op(&StringOperation::CMove, &mut test_stack, &mut context),
+            Err(ForthError::InvalidAddress)
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut context = string_context(&["abc", "abd", "ab"]);
+        for (stack, result) in [
+            ([0, 3, 0, 3], 0),
+            ([0, 3, 3, 3], -1),
+            ([3, 3, 0, 3], 1),
+            ([0, 3, 6, 2], 1),
+            ([6, 2, 0, 3], -1),
+        ] {
+            let mut test_stack = init_stack(&stack);
+            execute_string_op(&StringOperation::Compare, &mut test_stack, &mut context).unwrap();
+            assert_eq!(test_stack.data, &[result]);
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let mut context = string_context(&["hello world", "wor", "xyz"]);
+        let mut test_stack = init_stack(&[0, 11, 11, 3]);
+        execute_string_op(&StringOperation::Search, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[6, 5, -1]);
+
+        let mut test_stack = init_stack(&[0, 11, 14, 3]);
+        execute_string_op(&StringOperation::Search, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[0, 11, 0]);
+
+        let mut test_stack = init_stack(&[11, 3, 0, 11]);
+        execute_string_op(&StringOperation::Search, &mut test_stack, &mut context).unwrap();
+        assert_eq!(test_stack.data, &[11, 3, 0]);
+    }
+}
@@ -1,154 +1,147 @@
+#[cfg(feature = "float")]
+use crate::operations::float::execute_float_op;
 use crate::operations::{
     arithmetic::execute_arithmetic_op,
     boolean::execute_boolean_op,
-    conditional::{ConditionalOperation, execute_conditional_op},
+    double::execute_double_op,
+    file::execute_file_op,
     forth_operation::ForthOperation,
+    input::execute_input_op,
+    loops::{execute_loop_op, handle_do, handle_loop_end},
+    memory::execute_memory_op,
     output::execute_output_op,
+    return_stack_type::execute_return_stack_op,
     stack_type::execute_stack_op,
+    string::execute_string_op,
 };
 use crate::{
-    errors::{ForthError, print_error},
+    bytecode::Instruction,
+    context::ForthContext,
+    errors::ForthError,
     forth_value::ForthValue,
     stack::Stack,
     words::{
         dictionary::WordsDictionary,
-        word::{ForthWord, handle_word_execution},
+        word::{ForthWord, handle_word_call, handle_word_execution},
     },
 };
 
-/// Enum that represents the stage of the execution (mainly used in ifs and words to control).
-///
-/// The different ones are:
-///
-/// - Executing: the instruction is executing.
-/// - Skipping: tuple that contains the number of times (usually representing the depth) that the stage is skipped.
-///
-
-#[derive(PartialEq)]
-pub enum ExecutionStage {
-    Executing,
-    Skipping(usize),
-}
-
-/// Execute different operations depending on the ForthValue reference "val" received by parameter. It receives the stack aswell to pass to the different execute_operations to update it.
-/// The function also receives a reference to WordsDictionary to storage words, the current word that is executing, and a vector of the executed words to pass to execute_instruction.
+/// Execute different operations depending on the ForthValue reference "val" received by parameter, which is typed outside a word definition and a control structure.
+/// It receives the stack and the context aswell to pass to the different execute_operations to update them, and a reference to WordsDictionary to execute the words.
+/// Returns the first error found while executing the operation, so the caller can stop the execution.
 pub fn execute_other_operations(
     val: &ForthValue,
     stack: &mut Stack,
+    context: &mut ForthContext,
     dictionary: &WordsDictionary,
-    current_word: Option<String>,
-    executed_words: &mut Vec<String>,
-) {
+) -> Result<(), ForthError> {
     match val {
-        ForthValue::Operation(ForthOperation::Arithmetic(op)) => execute_arithmetic_op(op, stack),
-        ForthValue::Operation(ForthOperation::StackTypeOp(op)) => execute_stack_op(op, stack),
-        ForthValue::Operation(ForthOperation::Output(op)) => execute_output_op(op, stack),
-        ForthValue::Operation(ForthOperation::Boolean(op)) => execute_boolean_op(op, stack),
-        ForthValue::Number(n) => {
-            if let Err(e) = stack.push(*n) {
-                print_error(e);
-            }
+        ForthValue::Operation(op) => execute_operation(op, stack, context),
+        ForthValue::Number(n) => stack.push(*n),
+        ForthValue::DoubleNumber(first, second) | ForthValue::String(first, second) => {
+            stack.push(*first)?;
+            stack.push(*second)
         }
+        #[cfg(feature = "float")]
+        ForthValue::FloatNumber(f) => context.float_stack.push(*f),
+        ForthValue::Word(ForthWord::Recurse) => Err(ForthError::InvalidWord),
         ForthValue::Word(ForthWord::Start(word_name)) => {
-            if let Some(ref current) = current_word {
-                if current == word_name {
-                    return;
-                }
-            }
-            if let Some(definition) = dictionary.get_word(word_name) {
-                let mut execution_stage_stack = vec![ExecutionStage::Executing];
-                for val in definition {
-                    execute_instruction(
-                        val,
-                        stack,
-                        dictionary,
-                        &mut execution_stage_stack,
-                        Some(word_name.to_string()),
-                        executed_words,
-                    );
-                }
-            } else {
-                print_error(ForthError::UnknownWord);
-            }
+            handle_word_execution(word_name, stack, context, dictionary)
         }
-        _ => {}
+        _ => Ok(()),
     }
 }
 
-/// Execute different instructions depending on the ForthValue reference "val" received by parameter. It receives the stack aswell to pass to handle_executing_mode.
-/// The function also receives a reference to WordsDictionary to storage words, the current word that is executing, and a vector of the executed words to pass to handle_executing_mode.
-pub fn execute_instruction(
-    val: &ForthValue,
+/// Execute the ForthOperation received by parameter, passing the stack and the parts of the context it uses to its execute_operation.
+/// The conditional operations return invalid-word (for example, an 'ELSE' or 'THEN' typed alone), as they do in a definition without its 'IF',
+/// and the loop operations that jump return invalid-loop, since all of them must be compiled in a body.
+pub fn execute_operation(
+    op: &ForthOperation,
     stack: &mut Stack,
-    dictionary: &WordsDictionary,
-    execution_stage: &mut Vec<ExecutionStage>,
-    current_word: Option<String>,
-    executed_words: &mut Vec<String>,
-) {
-    match execution_stage.last().unwrap_or(&ExecutionStage::Executing) {
-        ExecutionStage::Executing => handle_executing_mode(
-            val,
-            stack,
-            dictionary,
-            execution_stage,
-            current_word,
-            executed_words,
-        ),
-        ExecutionStage::Skipping(_) => handle_skipping_mode(val, execution_stage),
-    }
-}
-
-fn handle_executing_mode(
-    val: &ForthValue,
-    stack: &mut Stack,
-    dictionary: &WordsDictionary,
-    execution_stage: &mut Vec<ExecutionStage>,
-    current_word: Option<String>,
-    executed_words: &mut Vec<String>,
-) {
-    match val {
-        ForthValue::Word(ForthWord::Start(word_name)) => {
-            handle_word_execution(&word_name.to_string(), stack, dictionary, executed_words);
+    context: &mut ForthContext,
+) -> Result<(), ForthError> {
+    match op {
+        ForthOperation::Arithmetic(op) => execute_arithmetic_op(op, stack),
+        ForthOperation::StackTypeOp(op) => execute_stack_op(op, stack),
+        ForthOperation::Output(op) => {
+            let base = context.memory.base();
+            execute_output_op(
+                op,
+                stack,
+                context.output.as_mut(),
+                &context.output_mode,
+                base,
+            )
         }
-        ForthValue::Operation(ForthOperation::Conditional(op)) => {
-            execute_conditional_op(op, stack, execution_stage);
+        ForthOperation::Boolean(op) => execute_boolean_op(op, stack),
+        ForthOperation::Double(op) => execute_double_op(op, stack),
+        ForthOperation::String(op) => execute_string_op(op, stack, context),
+        ForthOperation::Input(op) => execute_input_op(op, stack, context),
+        ForthOperation::File(op) => execute_file_op(op, stack, context),
+        #[cfg(feature = "float")]
+        ForthOperation::Float(op) => execute_float_op(op, stack, context),
+        ForthOperation::Memory(op) => execute_memory_op(op, stack, &mut context.memory),
+        ForthOperation::ReturnStackOp(op) => {
+            execute_return_stack_op(op, stack, &mut context.return_stack)
         }
-        _ => execute_other_operations(val, stack, dictionary, current_word, executed_words),
+        ForthOperation::Loop(op) => execute_loop_op(op, stack, &mut context.return_stack),
+        ForthOperation::Conditional(_) => Err(ForthError::InvalidWord),
     }
 }
 
-fn handle_skipping_mode(val: &ForthValue, execution_stage: &mut Vec<ExecutionStage>) {
-    if let ForthValue::Operation(ForthOperation::Conditional(op)) = val {
-        match op {
-            ConditionalOperation::Then => handle_skipping_then(execution_stage),
-            ConditionalOperation::Else => handle_skipping_else(execution_stage),
-            ConditionalOperation::If => handle_skipping_if(execution_stage),
-        }
-    }
-}
-
-fn handle_skipping_then(execution_stage: &mut Vec<ExecutionStage>) {
-    if let Some(ExecutionStage::Skipping(current_depth)) = execution_stage.last_mut() {
-        if *current_depth > 1 {
-            *current_depth -= 1;
-        } else {
-            execution_stage.pop();
-        }
-    }
+/// Execute a compiled body received by parameter as a slice of Instruction.
+/// The body is walked by its position ('pc'), which the jumps of the control structures move to the position they were compiled with.
+/// When the body finishes (or stops on its first error, which is returned), the frames it left in the return stack of the context received by parameter are discarded.
+pub fn execute_definition(
+    definition: &[Instruction],
+    stack: &mut Stack,
+    context: &mut ForthContext,
+    dictionary: &WordsDictionary,
+) -> Result<(), ForthError> {
+    let base = context.return_stack.len();
+    let result = execute_body(definition, stack, context, dictionary);
+    context.return_stack.truncate(base);
+    result
 }
 
-fn handle_skipping_else(execution_stage: &mut [ExecutionStage]) {
-    if let Some(ExecutionStage::Skipping(depth)) = execution_stage.last_mut() {
-        if *depth == 1 {
-            if let Some(last) = execution_stage.last_mut() {
-                *last = ExecutionStage::Executing;
+fn execute_body(
+    definition: &[Instruction],
+    stack: &mut Stack,
+    context: &mut ForthContext,
+    dictionary: &WordsDictionary,
+) -> Result<(), ForthError> {
+    let mut pc = 0;
+    while let Some(instruction) = definition.get(pc) {
+        pc += 1;
+        match instruction {
+            Instruction::Literal(n) => stack.push(*n)?,
+            #[cfg(feature = "float")]
+            Instruction::FloatLiteral(f) => context.float_stack.push(*f)?,
+            Instruction::Operation(op) => execute_operation(op, stack, context)?,
+            Instruction::Call(index) => handle_word_call(*index, stack, context, dictionary)?,
+            Instruction::CallByName(word_name) => {
+                handle_word_execution(word_name, stack, context, dictionary)?
             }
+            Instruction::Jump(target) => pc = *target,
+            Instruction::JumpIfZero(target) => {
+                if stack.pop()? == 0 {
+                    pc = *target;
+                }
+            }
+            Instruction::Do => handle_do(stack, &mut context.return_stack)?,
+            Instruction::Loop(target) => {
+                if handle_loop_end(1, &mut context.return_stack)? {
+                    pc = *target;
+                }
+            }
+            Instruction::PlusLoop(target) => {
+                let step = stack.pop()?;
+                if handle_loop_end(step, &mut context.return_stack)? {
+                    pc = *target;
+                }
+            }
+            Instruction::Exit => break,
         }
     }
-}
-
-fn handle_skipping_if(execution_stage: &mut [ExecutionStage]) {
-    if let Some(ExecutionStage::Skipping(depth)) = execution_stage.last_mut() {
-        *depth += 1;
-    }
+    Ok(())
 }
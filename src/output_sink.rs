@@ -0,0 +1,111 @@
+use crate::errors::ForthError;
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{self, Write},
+};
+
+/// Trait implemented by the destinations of the text printed by the program ('.', 'EMIT', 'CR', '."' and '.S').
+/// The text kept in memory (if any) can be consulted with contents, so the printed text can be checked.
+pub trait OutputSink: Debug {
+    /// Function used to write the text received by parameter. Returns a generic error if it could not be written.
+    fn write_text(&mut self, text: &str) -> Result<(), ForthError>;
+
+    /// Function that returns the text written so far, if it is kept in memory. By default it is not.
+    fn contents(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// This struct writes the text to the standard output, flushing it after each write so it is shown immediately.
+#[derive(Debug, Default)]
+pub struct StdoutOutput;
+
+impl OutputSink for StdoutOutput {
+    fn write_text(&mut self, text: &str) -> Result<(), ForthError> {
+        let mut stdout = io::stdout().lock();
+        stdout
+            .write_all(text.as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(|_| ForthError::Generic("Impossible to write output".to_string()))
+    }
+}
+
+/// This struct keeps the text in memory (in the text field), so it can be consulted later (for example, in tests).
+#[derive(Debug, Default)]
+pub struct BufferOutput {
+    pub text: String,
+}
+
+impl BufferOutput {
+    /// Function used to build the structure, without text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OutputSink for BufferOutput {
+    fn write_text(&mut self, text: &str) -> Result<(), ForthError> {
+        self.text.push_str(text);
+        Ok(())
+    }
+
+    fn contents(&self) -> Option<&str> {
+        Some(&self.text)
+    }
+}
+
+/// This struct writes the text to a file, which is created (or truncated) when the structure is built.
+#[derive(Debug)]
+pub struct FileOutput {
+    file: File,
+}
+
+impl FileOutput {
+    /// Function used to build the structure by creating the file of the path received by parameter. Returns a generic error if it could not be created.
+    pub fn create(path: &str) -> Result<Self, ForthError> {
+        let file = File::create(path)
+            .map_err(|_| ForthError::Generic(format!("Impossible to create {path}")))?;
+        Ok(FileOutput { file })
+    }
+}
+
+impl OutputSink for FileOutput {
+    fn write_text(&mut self, text: &str) -> Result<(), ForthError> {
+        self.file
+            .write_all(text.as_bytes())
+            .map_err(|_| ForthError::Generic("Impossible to write output".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferOutput, FileOutput, OutputSink, StdoutOutput};
+    use std::fs;
+
+    #[test]
+    fn test_buffer_output() {
+        let mut output = BufferOutput::new();
+        output.write_text("hello ").unwrap();
+        output.write_text("world").unwrap();
+        assert_eq!(output.contents(), Some("hello world"));
+    }
+
+    #[test]
+    fn test_stdout_output_has_no_contents() {
+        let mut output = StdoutOutput;
+        output.write_text("").unwrap();
+        assert_eq!(output.contents(), None);
+    }
+
+    #[test]
+    fn test_file_output() {
+        let path = std::env::temp_dir().join("forth_file_output_test.txt");
+        let path = path.to_str().unwrap();
+        let mut output = FileOutput::create(path).unwrap();
+        output.write_text("1 2\n").unwrap();
+        drop(output);
+        assert_eq!(fs::read_to_string(path).unwrap(), "1 2\n");
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    cell::{Cell, CellConfig},
+    errors::ForthError,
+    operations::loops::LoopFrame,
+};
+
+/// Each word executing nests the Rust calls that run its body, so the max_call_depth is bounded by this limit to keep a deep recursion
+/// from overflowing the native stack of the thread (even the 2MB of a spawned thread) before it raises call-depth-exceeded.
+pub const MAX_CALL_DEPTH_LIMIT: usize = 512;
+
+/// Enum that represents the frames that can be kept in the return stack.
+///
+/// The different ones are:
+///
+/// - Value: tuple that contains a value moved from the Stack ('>R').
+/// - Loop: tuple that contains a loop that is being executed.
+/// - Call: tuple that contains the index (in the dictionary) of the definition of a word that is being executed.
+///
+
+#[derive(Debug)]
+pub enum ReturnFrame {
+    Value(Cell),
+    Loop(LoopFrame),
+    Call(usize),
+}
+
+/// This struct is the return stack of the program, where the words being executed, the loops and the values moved with '>R' are kept.
+/// It contains the frames (as pub so they can be accessed by other modules). The max_frames field is used to bound the structure by the size received in the program input,
+/// and the max_call_depth field bounds how many words can be executing at the same time (counted by call_depth), so a recursion that never ends raises an error.
+#[derive(Debug)]
+pub struct ReturnStack {
+    pub frames: Vec<ReturnFrame>,
+    max_frames: usize,
+    call_depth: usize,
+    max_call_depth: usize,
+}
+
+impl ReturnStack {
+    /// Function used to build the structure with cells of 16 bits. Receives a size in bytes that sets the max_frames of the ReturnStack, and the max_call_depth.
+    pub fn new(size: usize, max_call_depth: usize) -> Self {
+        Self::with_cells(size, max_call_depth, &CellConfig::default())
+    }
+
+    /// Function used to build the structure. Receives a size in bytes that sets the max_frames of the ReturnStack (by the bytes of each cell of the CellConfig received by parameter),
+    /// and the max_call_depth (which can not be greater than MAX_CALL_DEPTH_LIMIT).
+    pub fn with_cells(size: usize, max_call_depth: usize, cell: &CellConfig) -> Self {
+        ReturnStack {
+            frames: Vec::new(),
+            max_frames: size / cell.bytes(),
+            call_depth: 0,
+            max_call_depth: max_call_depth.min(MAX_CALL_DEPTH_LIMIT),
+        }
+    }
+
+    /// Function to add a frame to the top of the ReturnStack. Returns Ok if possible or return-stack-overflow error if it exceeds the structure max_frames.
+    pub fn push(&mut self, frame: ReturnFrame) -> Result<(), ForthError> {
+        if self.frames.len() >= self.max_frames {
+            Err(ForthError::ReturnStackOverflow)
+        } else {
+            if matches!(frame, ReturnFrame::Call(_)) {
+                self.call_depth += 1;
+            }
+            self.frames.push(frame);
+            Ok(())
+        }
+    }
+
+    /// Function to add the call frame of the definition (by its index) received by parameter. Returns call-depth-exceeded if there are already max_call_depth words executing,
+    /// or return-stack-overflow if it exceeds the structure max_frames.
+    pub fn push_call(&mut self, index: usize) -> Result<(), ForthError> {
+        if self.call_depth >= self.max_call_depth {
+            return Err(ForthError::CallDepthExceeded);
+        }
+        self.push(ReturnFrame::Call(index))
+    }
+
+    /// Function to get the value on top of the ReturnStack. Returns return-stack-underflow if the top frame is not a value
+    /// (so a word can not take the values of the word that called it, nor a loop index).
+    pub fn pop_value(&mut self) -> Result<Cell, ForthError> {
+        let value = self.peek_value()?;
+        self.frames.pop();
+        Ok(value)
+    }
+
+    /// Function to get a copy of the value on top of the ReturnStack without removing it. Returns return-stack-underflow if the top frame is not a value.
+    pub fn peek_value(&self) -> Result<Cell, ForthError> {
+        match self.frames.last() {
+            Some(ReturnFrame::Value(value)) => Ok(*value),
+            _ => Err(ForthError::ReturnStackUnderflow),
+        }
+    }
+
+    /// Function to get a mutable reference to the loop on top of the ReturnStack, if the top frame is a loop.
+    pub fn last_loop_mut(&mut self) -> Option<&mut LoopFrame> {
+        match self.frames.last_mut() {
+            Some(ReturnFrame::Loop(frame)) => Some(frame),
+            _ => None,
+        }
+    }
+
+    /// Function to remove the loop on top of the ReturnStack. Returns None if the top frame is not a loop.
+    pub fn pop_loop(&mut self) -> Option<LoopFrame> {
+        match self.frames.pop() {
+            Some(ReturnFrame::Loop(frame)) => Some(frame),
+            Some(other) => {
+                self.frames.push(other);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Function that returns the index of the counted loop at the depth received by parameter (0 is the innermost one).
+    /// Only the loops of the word being executed are considered, so the search stops at the first call frame.
+    pub fn loop_index(&self, depth: usize) -> Option<Cell> {
+        self.frames
+            .iter()
+            .rev()
+            .take_while(|frame| !matches!(frame, ReturnFrame::Call(_)))
+            .filter_map(|frame| match frame {
+                ReturnFrame::Loop(LoopFrame::Counted { index, .. }) => Some(*index),
+                _ => None,
+            })
+            .nth(depth)
+    }
+
+    /// Function that returns the amount of frames of the ReturnStack.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Function that returns true if the ReturnStack has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Function used to discard the frames above the length received by parameter, such as the ones left by a body that has finished.
+    pub fn truncate(&mut self, len: usize) {
+        let removed_calls = self
+            .frames
+            .iter()
+            .skip(len)
+            .filter(|frame| matches!(frame, ReturnFrame::Call(_)))
+            .count();
+        self.call_depth -= removed_calls;
+        self.frames.truncate(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_CALL_DEPTH_LIMIT, ReturnFrame, ReturnStack};
+    use crate::{
+        cell::{CellConfig, CellWidth},
+        errors::ForthError,
+        operations::loops::LoopFrame,
+    };
+
+    #[test]
+    fn test_push_pop_values() {
+        let mut return_stack = ReturnStack::new(100, 10);
+        return_stack.push(ReturnFrame::Value(3)).unwrap();
+        assert_eq!(return_stack.peek_value(), Ok(3));
+        assert_eq!(return_stack.pop_value(), Ok(3));
+        assert_eq!(
+            return_stack.pop_value(),
+            Err(ForthError::ReturnStackUnderflow)
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut return_stack = ReturnStack::new(4, 10);
+        return_stack.push(ReturnFrame::Value(1)).unwrap();
+        return_stack.push(ReturnFrame::Value(2)).unwrap();
+        assert_eq!(
+            return_stack.push(ReturnFrame::Value(3)),
+            Err(ForthError::ReturnStackOverflow)
+        );
+    }
+
+    #[test]
+    fn test_overflow_with_cells_of_64_bits() {
+        let cell = CellConfig {
+            width: CellWidth::Bits64,
+            ..Default::default()
+        };
+        let mut return_stack = ReturnStack::with_cells(16, 10, &cell);
+        return_stack.push(ReturnFrame::Value(1)).unwrap();
+        return_stack.push(ReturnFrame::Value(2)).unwrap();
+        assert_eq!(
+            return_stack.push(ReturnFrame::Value(3)),
+            Err(ForthError::ReturnStackOverflow)
+        );
+    }
+
+    #[test]
+    fn test_values_are_not_taken_from_other_frames() {
+        let mut return_stack = ReturnStack::new(100, 10);
+        return_stack.push(ReturnFrame::Value(1)).unwrap();
+        return_stack.push_call(0).unwrap();
+        assert_eq!(
+            return_stack.pop_value(),
+            Err(ForthError::ReturnStackUnderflow)
+        );
+        assert!(return_stack.pop_loop().is_none());
+        assert_eq!(return_stack.len(), 2);
+    }
+
+    #[test]
+    fn test_call_depth() {
+        let mut return_stack = ReturnStack::new(100, 2);
+        return_stack.push_call(0).unwrap();
+        return_stack.push(ReturnFrame::Value(1)).unwrap();
+        return_stack.push_call(0).unwrap();
+        assert_eq!(
+            return_stack.push_call(0),
+            Err(ForthError::CallDepthExceeded)
+        );
+        return_stack.truncate(1);
+        return_stack.push_call(1).unwrap();
+        assert_eq!(return_stack.len(), 2);
+
+        let mut return_stack = ReturnStack::new(4096, 10_000);
+        for _ in 0..MAX_CALL_DEPTH_LIMIT {
+            return_stack.push_call(0).unwrap();
+        }
+        assert_eq!(
+            return_stack.push_call(0),
+            Err(ForthError::CallDepthExceeded)
+        );
+    }
+
+    #[test]
+    fn test_loop_index_stops_at_call() {
+        let mut return_stack = ReturnStack::new(100, 10);
+        return_stack
+            .push(ReturnFrame::Loop(LoopFrame::Counted { index: 1, limit: 5 }))
+            .unwrap();
+        return_stack.push_call(0).unwrap();
+        assert_eq!(return_stack.loop_index(0), None);
+        return_stack.truncate(1);
+        assert_eq!(return_stack.loop_index(0), Some(1));
+    }
+}
@@ -1,29 +1,45 @@
-use crate::errors::ForthError;
+use crate::{
+    cell::{Cell, CellConfig},
+    errors::ForthError,
+};
 use std::{fs, io};
 
 const STACK_REST_PATHNAME: &str = "stack.fth";
 
 /// This struct is the main of the program. It is used everywhere to save and get an element.
-/// It contains data (as pub so it can be accessed by other modules) of i16 values. The max_elements field is used to initialize the struct by the size received in the program input
+/// It contains data (as pub so it can be accessed by other modules) of Cell values. The max_elements field is used to initialize the struct by the size received in the program input,
+/// and the cell field sets the width of the values and what happens when a result does not fit in them.
 
 #[derive(Debug)]
 pub struct Stack {
-    pub data: Vec<i16>,
+    pub data: Vec<Cell>,
     max_elements: usize,
+    cell: CellConfig,
 }
 
 impl Stack {
-    /// Function used to build the structure. Receives a size that sets the max_elements of the Stack
+    /// Function used to build the structure with cells of 16 bits that wrap around. Receives a size in bytes that sets the max_elements of the Stack
     pub fn new(size: usize) -> Self {
-        let max_elements = size / 2;
+        Self::with_cells(size, CellConfig::default())
+    }
+
+    /// Function used to build the structure. Receives a size in bytes that sets the max_elements of the Stack (by the bytes of each cell), and the CellConfig.
+    pub fn with_cells(size: usize, cell: CellConfig) -> Self {
+        let max_elements = size / cell.bytes();
         Stack {
             data: Vec::with_capacity(max_elements),
             max_elements,
+            cell,
         }
     }
 
-    /// Function to add an i16 value to the top of the Stack. Returns Ok if possible or stack-overflow error if it exceeds the structure max_elements.
-    pub fn push(&mut self, value: i16) -> Result<(), ForthError> {
+    /// Function that returns the CellConfig of the Stack.
+    pub fn cell(&self) -> &CellConfig {
+        &self.cell
+    }
+
+    /// Function to add a Cell value to the top of the Stack. Returns Ok if possible or stack-overflow error if it exceeds the structure max_elements.
+    pub fn push(&mut self, value: Cell) -> Result<(), ForthError> {
         if self.data.len() >= self.max_elements {
             Err(ForthError::StackOverflow)
         } else {
@@ -32,19 +48,41 @@ impl Stack {
         }
     }
 
-    /// Function to get the top i16 value of the Stack. Returns the value if possible or stack-underflow if the element does not exists.
-    pub fn pop(&mut self) -> Result<i16, ForthError> {
+    /// Function to add the result of an operation (computed with a wider type, so it can not overflow) to the top of the Stack, fitting it in a cell (see CellConfig::fit).
+    /// Returns overflow if it does not fit and the policy is Error, or stack-overflow error if it exceeds the structure max_elements.
+    pub fn push_wide(&mut self, value: i128) -> Result<(), ForthError> {
+        let value = self.cell.fit(value)?;
+        self.push(value)
+    }
+
+    /// Function to add a double-cell number to the top of the Stack, as its low cell followed by its high cell (see CellConfig::split_double).
+    /// Returns stack-overflow error if it exceeds the structure max_elements.
+    pub fn push_double(&mut self, value: i128) -> Result<(), ForthError> {
+        let (low, high) = self.cell.split_double(value);
+        self.push(low)?;
+        self.push(high)
+    }
+
+    /// Function to get the double-cell number of the two top Cell values of the Stack (see push_double). Returns stack-underflow if any of them does not exists.
+    pub fn pop_double(&mut self) -> Result<i128, ForthError> {
+        let high = self.pop()?;
+        let low = self.pop()?;
+        Ok(self.cell.join_double(low, high))
+    }
+
+    /// Function to get the top Cell value of the Stack. Returns the value if possible or stack-underflow if the element does not exists.
+    pub fn pop(&mut self) -> Result<Cell, ForthError> {
         self.data.pop().ok_or(ForthError::StackUnderflow)
     }
 
-    /// Function to get a reference of the top i16 value of the Stack. Returns a reference to the value if possible or stack-underflow if the element does not exists.
+    /// Function to get a reference of the top Cell value of the Stack. Returns a reference to the value if possible or stack-underflow if the element does not exists.
     /// Unlike pop, peek does not mutate the stack.
-    pub fn peek(&self) -> Result<&i16, ForthError> {
+    pub fn peek(&self) -> Result<&Cell, ForthError> {
         self.data.last().ok_or(ForthError::StackUnderflow)
     }
 
     /// Function used to write the rest of the stack to a stack.fth file. Returns Ok(true), letting the error be handled by the function that call it.
-    pub fn write_into_file(&mut self) -> io::Result<bool> {
+    pub fn write_into_file(&self) -> io::Result<bool> {
         let stack_results: Vec<String> = self.data.iter().map(|&item| item.to_string()).collect();
         fs::write(STACK_REST_PATHNAME, stack_results.join(" "))?;
         Ok(true)
@@ -1,43 +1,191 @@
+#[cfg(feature = "float")]
+use crate::operations::float::{parse_float, parse_float_op};
 use crate::operations::{
     arithmetic::parse_arithmetic, boolean::parse_boolean, conditional::parse_conditional,
-    output::parse_output, stack_type::parse_stack_op,
+    double::parse_double_op, file::parse_file_op, input::parse_input_op, loops::parse_loop,
+    memory::parse_memory, output::parse_output, return_stack_type::parse_return_stack_op,
+    stack_type::parse_stack_op, string::parse_string_op,
 };
 use crate::operations::{forth_operation::ForthOperation, output::OutputOperation};
+use crate::utils::read_file;
 use crate::words::{
     dictionary::WordsDictionary,
-    word::{ForthWord, handle_word_definition, handle_word_end, parse_word},
+    word::{
+        ForthWord, handle_constant_definition, handle_variable_definition, handle_word_definition,
+        handle_word_end, parse_word,
+    },
 };
 use crate::{
-    errors::{ForthError, print_error},
+    bytecode::{compile_definition, control_nesting, opens_control_structure},
+    cell::{Cell, CellConfig},
+    context::ForthContext,
+    data_memory::DataMemory,
+    errors::ForthError,
     forth_value::ForthValue,
 };
 use crate::{
-    other_executions::{ExecutionStage, execute_instruction},
+    other_executions::{execute_definition, execute_other_operations},
     stack::Stack,
 };
+use std::fs;
 use std::iter::Peekable;
+use std::path::{Path, PathBuf};
 use std::str::Chars;
 
+/// This struct keeps the state of the reading between lines, so word definitions, control structures, strings (dot-quote and S-quote) and parenthesized comments can span several of them.
+/// It contains:
+/// - defining_word: flag that indicates if a word is being defined.
+/// - word_name: the name of the word being defined.
+/// - definition: the values of the word being defined, or of the control structure (if/else/then or loop) typed outside a word definition that is being collected.
+/// - pending_string: the token of a string that was not closed yet, with its prefix (`."` or `S"`) and the text read so far.
+/// - pending_comment: flag that indicates if a parenthesized comment was not closed yet.
+/// - allow_self_calls: flag that indicates if a word can call itself by its name (as 'RECURSE' does). It is kept when the state is reset.
+/// - sources: the canonical paths of the source files being read, the innermost one last, so the relative paths they include are searched next to it. It is kept when the state is reset.
+/// - included: the canonical paths of the source files already loaded, so 'REQUIRE' does not load them again. It is kept when the state is reset.
+#[derive(Debug, Default)]
+pub struct ReadingState {
+    defining_word: bool,
+    word_name: String,
+    definition: Vec<ForthValue>,
+    pending_string: Option<String>,
+    pending_comment: bool,
+    allow_self_calls: bool,
+    sources: Vec<PathBuf>,
+    included: Vec<PathBuf>,
+}
+
+impl ReadingState {
+    /// Function used to build the structure, with nothing pending.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Function used to build the structure, with nothing pending, setting if the words can call themselves by their name.
+    pub fn with_self_calls(allow_self_calls: bool) -> Self {
+        ReadingState {
+            allow_self_calls,
+            ..Self::default()
+        }
+    }
+
+    /// Function used to discard everything that is pending (for example, after an error).
+    pub fn reset(&mut self) {
+        *self = ReadingState {
+            allow_self_calls: self.allow_self_calls,
+            sources: std::mem::take(&mut self.sources),
+            included: std::mem::take(&mut self.included),
+            ..Self::default()
+        };
+    }
+
+    /// Function used when the source file of the path received by parameter starts being read, so the files it includes are searched next to it.
+    pub fn push_source(&mut self, path: &Path) {
+        self.sources
+            .push(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()));
+    }
+
+    /// Function used when the innermost source file being read is finished, marking it as loaded if it was read without errors (loaded), so 'REQUIRE' does not skip a file whose words were not all defined.
+    pub fn pop_source(&mut self, loaded: bool) {
+        if let Some(path) = self.sources.pop()
+            && loaded
+            && !self.included.contains(&path)
+        {
+            self.included.push(path);
+        }
+    }
+
+    /// Function used when there is no more input to read. Returns invalid-word if a word definition, a string or a parenthesized comment was left open,
+    /// or invalid-loop if a control structure typed outside a word definition was left open. In both cases the pending state is discarded.
+    pub fn finish(&mut self) -> Result<(), ForthError> {
+        let result = if self.defining_word || self.pending_string.is_some() || self.pending_comment
+        {
+            Err(ForthError::InvalidWord)
+        } else if !self.definition.is_empty() {
+            Err(ForthError::InvalidLoop)
+        } else {
+            Ok(())
+        };
+        self.reset();
+        result
+    }
+}
+
+/// Function that collects the characters received by parameter into "text" until a closing `"` is found.
+/// Returns true if the closing `"` was found, or false if the characters ended before it.
+fn collect_quoted_text(chars: &mut Peekable<Chars>, text: &mut String) -> bool {
+    for c in chars.by_ref() {
+        if c == '"' {
+            return true;
+        }
+        text.push(c);
+    }
+    false
+}
+
+/// Function that skips the characters received by parameter until a closing `)` is found (which is skipped aswell).
+/// Returns true if the closing `)` was found, or false if the characters ended before it.
+fn skip_comment(chars: &mut Peekable<Chars>) -> bool {
+    chars.by_ref().any(|c| c == ')')
+}
+
 /// Function to handle the process of DotQuote expression. This process is constructed by:
 /// 1. Consuming the initial `.` and optional leading spaces
 /// 2. Collecting all characters received by parameter until a closing `"` is found
 /// 3. Adding the formatted token (e.g., `."message"`) to the tokens vector received by parameter.
-pub fn tokenize_dot_quote(chars: &mut Peekable<Chars>, tokens: &mut Vec<String>) {
+///    If the closing `"` is not found, the text is kept as pending in the ReadingState, so it can continue in the next line.
+pub fn tokenize_dot_quote(
+    chars: &mut Peekable<Chars>,
+    tokens: &mut Vec<String>,
+    state: &mut ReadingState,
+) {
     chars.next();
-    let mut dot_quote = String::new();
+    let mut dot_quote = String::from(".\"");
 
     while let Some(' ') = chars.peek() {
         chars.next();
     }
 
-    for c in chars.by_ref() {
-        if c == '"' {
-            break;
-        }
-        dot_quote.push(c);
+    finish_quoted_token(
+        collect_quoted_text(chars, &mut dot_quote),
+        dot_quote,
+        tokens,
+        state,
+    );
+}
+
+/// Function to handle the process of an S-quote string (`S" text"`), like tokenize_dot_quote does, but skipping only the space that separates `S"` from its text.
+/// The formatted token (e.g., `S"message"`) is added to the tokens vector received by parameter, or kept as pending in the ReadingState if the closing `"` is not found.
+pub fn tokenize_s_quote(
+    chars: &mut Peekable<Chars>,
+    tokens: &mut Vec<String>,
+    state: &mut ReadingState,
+) {
+    chars.next();
+    let mut s_quote = String::from("S\"");
+    if let Some(' ') = chars.peek() {
+        chars.next();
     }
 
-    tokens.push(format!(".\"{}", dot_quote));
+    finish_quoted_token(
+        collect_quoted_text(chars, &mut s_quote),
+        s_quote,
+        tokens,
+        state,
+    );
+}
+
+/// Function that adds the quoted token received by parameter to the tokens if it was closed, or keeps it as pending in the ReadingState otherwise.
+fn finish_quoted_token(
+    closed: bool,
+    token: String,
+    tokens: &mut Vec<String>,
+    state: &mut ReadingState,
+) {
+    if closed {
+        tokens.push(token);
+    } else {
+        state.pending_string = Some(token);
+    }
 }
 
 /// Function that pushes the current token to the tokens if it is not empty.
@@ -47,25 +195,73 @@ pub fn handle_token_char(cur_tok: String, tokens: &mut Vec<String>) {
     }
 }
 
+/// Function that handles a token received by parameter that has been completely read (it is followed by a whitespace or the end of the line).
+/// A `\` token starts a comment until the end of the line, so the rest of the characters are discarded, and a `(` token starts a comment until the next `)`,
+/// which is left pending in the ReadingState if it is not closed in this line. Any other token is pushed to the tokens.
+fn handle_complete_token(
+    cur_tok: String,
+    chars: &mut Peekable<Chars>,
+    tokens: &mut Vec<String>,
+    state: &mut ReadingState,
+) {
+    match cur_tok.as_str() {
+        "\\" => chars.by_ref().for_each(drop),
+        "(" => state.pending_comment = !skip_comment(chars),
+        _ => handle_token_char(cur_tok, tokens),
+    }
+}
+
 /// Function that 'tokenize' the input received as &str, returning a vector of String.
 /// This function iterates the characters, processing each one to return its interpretation in the following way:
 /// 1. Splitting on whitespace (spaces and tabs)
-/// 2. Handling special dot-quote strings (`."...`) as single tokens
-/// 3. Preserving all other character sequences as distinct tokens
+/// 2. Handling special dot-quote strings (`."...`) and S-quote strings (`S" ...`) as single tokens
+/// 3. Skipping the comments, from a `\` token to the end of the line or from a `(` token to the next `)`
+/// 4. Preserving all other character sequences as distinct tokens
+///
+/// An unclosed string takes the rest of the input as its text, and an unclosed comment is ignored.
 pub fn tokenize(input: &str) -> Vec<String> {
+    let mut state = ReadingState::new();
+    let mut tokens = tokenize_with_state(input, &mut state);
+    if let Some(quoted) = state.pending_string.take() {
+        tokens.push(quoted);
+    }
+    tokens
+}
+
+/// Function that 'tokenize' a line received as &str like tokenize does, but continuing the string left pending by the previous line (if any),
+/// which is joined to this one by a line break, or the parenthesized comment left pending by it.
+/// A string or a comment that is not closed in this line is left pending in the ReadingState.
+pub fn tokenize_with_state(input: &str, state: &mut ReadingState) -> Vec<String> {
     let mut tokens = Vec::new();
     let mut chars = input.chars().peekable();
     let mut current_token = String::new();
 
+    if let Some(mut quoted) = state.pending_string.take() {
+        quoted.push('\n');
+        if !collect_quoted_text(&mut chars, &mut quoted) {
+            state.pending_string = Some(quoted);
+            return tokens;
+        }
+        tokens.push(quoted);
+    }
+
+    if state.pending_comment && !skip_comment(&mut chars) {
+        return tokens;
+    }
+    state.pending_comment = false;
+
     while let Some(c) = chars.next() {
         match c {
             '.' if chars.peek() == Some(&'"') => {
                 handle_token_char(current_token, &mut tokens);
                 current_token = String::new();
-                tokenize_dot_quote(&mut chars, &mut tokens);
+                tokenize_dot_quote(&mut chars, &mut tokens, state);
+            }
+            's' | 'S' if current_token.is_empty() && chars.peek() == Some(&'"') => {
+                tokenize_s_quote(&mut chars, &mut tokens, state);
             }
             ' ' | '\t' => {
-                handle_token_char(current_token, &mut tokens);
+                handle_complete_token(current_token, &mut chars, &mut tokens, state);
                 current_token = String::new();
             }
             _ => {
@@ -74,140 +270,296 @@ pub fn tokenize(input: &str) -> Vec<String> {
         }
     }
 
-    handle_token_char(current_token, &mut tokens);
+    handle_complete_token(current_token, &mut chars, &mut tokens, state);
 
     tokens
 }
 
 /// Function that parse a token.
-/// Receives a token as &str and &WordsDictionary, returning its associated ForthValue.
+/// Receives a token as &str, &WordsDictionary, the CellConfig used to fit the numbers and the number base used to parse them, returning its associated ForthValue.
+/// Returns overflow if the token is a number that does not fit in a cell and the overflow policy is Error.
 /// This function attempts to interpret a token in the following priority order:
 /// 1. Dot-quote strings (e.g., `."message"`)
 /// 2. User-defined words (checks dictionary)
-/// 3. Numeric literals
-/// 4. Built-in operations (arithmetic, stack, output, boolean, conditional)
-/// 5. Word definitions (start/end markers)
-pub fn parse_token(token: &str, dictionary: &WordsDictionary) -> ForthValue {
+/// 3. Built-in operations (arithmetic, stack, output, boolean, double-cell, conditional, loop, memory, return stack, string, input, file and, with the "float" feature, floating-point)
+/// 4. Word definitions (start/end markers)
+/// 5. Numeric literals, double-cell ones (ended by '.') and, with the "float" feature, float ones (with an exponent, as '1.5e0')
+pub fn parse_token(
+    token: &str,
+    dictionary: &WordsDictionary,
+    cell: &CellConfig,
+    base: u32,
+) -> Result<ForthValue, ForthError> {
     if let Some(quoted_text) = token.strip_prefix(".\"") {
-        return ForthValue::Operation(ForthOperation::Output(OutputOperation::DotQuote(
-            quoted_text.to_string(),
+        return Ok(ForthValue::Operation(ForthOperation::Output(
+            OutputOperation::DotQuote(quoted_text.to_string()),
         )));
     }
 
     let uppercased_token = token.to_uppercase();
 
     if dictionary.word_already_defined(&uppercased_token) {
-        return ForthValue::Word(ForthWord::Start(uppercased_token));
+        return Ok(ForthValue::Word(ForthWord::Start(uppercased_token)));
     }
 
     if let Some(value) = parse_arithmetic(&uppercased_token) {
-        return value;
+        return Ok(value);
     }
     if let Some(value) = parse_stack_op(&uppercased_token) {
-        return value;
+        return Ok(value);
     }
     if let Some(value) = parse_output(&uppercased_token) {
-        return value;
+        return Ok(value);
     }
     if let Some(value) = parse_boolean(&uppercased_token) {
-        return value;
+        return Ok(value);
+    }
+    if let Some(value) = parse_double_op(&uppercased_token) {
+        return Ok(value);
     }
     if let Some(value) = parse_conditional(&uppercased_token) {
-        return value;
+        return Ok(value);
+    }
+    if let Some(value) = parse_loop(&uppercased_token) {
+        return Ok(value);
+    }
+    if let Some(value) = parse_memory(&uppercased_token) {
+        return Ok(value);
+    }
+    if let Some(value) = parse_return_stack_op(&uppercased_token) {
+        return Ok(value);
+    }
+    if let Some(value) = parse_string_op(&uppercased_token) {
+        return Ok(value);
+    }
+    if let Some(value) = parse_input_op(&uppercased_token) {
+        return Ok(value);
+    }
+    if let Some(value) = parse_file_op(&uppercased_token) {
+        return Ok(value);
+    }
+    #[cfg(feature = "float")]
+    if let Some(value) = parse_float_op(&uppercased_token) {
+        return Ok(value);
     }
     if let Some(value) = parse_word(&uppercased_token) {
-        return value;
+        return Ok(value);
     }
 
-    match token.parse::<i16>() {
-        Ok(num) => ForthValue::Number(num),
-        Err(_) => ForthValue::Word(ForthWord::Start(uppercased_token)),
+    if let Some(num) = cell.parse_number(token, base) {
+        return Ok(ForthValue::Number(num?));
+    }
+    if let Some((low, high)) = cell.parse_double(token, base) {
+        return Ok(ForthValue::DoubleNumber(low, high));
+    }
+    #[cfg(feature = "float")]
+    if let Some(value) = parse_float(token, base) {
+        return Ok(ForthValue::FloatNumber(value));
     }
+    Ok(ForthValue::Word(ForthWord::Start(uppercased_token)))
+}
+
+/// Function that stores the text of an S-quote string received by parameter in the DataMemory received by parameter, when it is read.
+/// When it is compiled (in a word definition, or in a control structure being collected) its cells are reserved, so the body keeps the same string however many others it reads or
+/// however many times it runs, and otherwise it is kept in a transient buffer (so typing it again does not use more cells).
+/// Returns the ForthValue that pushes its address and length, or the error of storing it.
+fn store_string(
+    text: &str,
+    memory: &mut DataMemory,
+    compiling: bool,
+) -> Result<ForthValue, ForthError> {
+    let address = if compiling {
+        memory.store_string(text)?
+    } else {
+        memory.store_transient(text)?
+    };
+    Ok(ForthValue::String(address, text.chars().count() as Cell))
 }
 
 /// Function used to handle values that are not a word.
-/// Receives the ForthValue, a flag that indicates if a word is or is not defined, a mutable ForthValue vector "definition" to add a value if its defining a word,
-/// and a mutable Stack, WordsDictionary, the executed words (vector of String), and the execution stage vector to pass directly to execute_instruction function.
+/// Receives the ForthValue, the ReadingState (that indicates if a word is or is not defined, and has the "definition" to add a value if its defining a word),
+/// and a mutable Stack, ForthContext and WordsDictionary to pass directly to execute_other_operations function.
+/// Control structures outside a word definition are also collected in "definition" until they are closed, and then compiled and executed as a word body would be.
+/// Returns the error of the executed value, if any.
 pub fn handle_other_token(
     value: ForthValue,
-    flag_defining_word: bool,
-    definition: &mut Vec<ForthValue>,
+    state: &mut ReadingState,
     stack: &mut Stack,
+    context: &mut ForthContext,
     dictionary: &mut WordsDictionary,
-    executed_words: &mut Vec<String>,
-    execution_stage_stack: &mut Vec<ExecutionStage>,
-) {
-    if flag_defining_word {
-        definition.push(value);
+) -> Result<(), ForthError> {
+    if state.defining_word {
+        state.definition.push(value);
+    } else if !state.definition.is_empty() || opens_control_structure(&value) {
+        state.definition.push(value);
+        if control_nesting(&state.definition) == 0 {
+            let values = std::mem::take(&mut state.definition);
+            let body = compile_definition(values, dictionary, None, false)?;
+            return execute_definition(&body, stack, context, dictionary);
+        }
     } else {
-        execute_instruction(
-            &value,
-            stack,
-            dictionary,
-            execution_stage_stack,
-            None,
-            executed_words,
-        );
+        return execute_other_operations(&value, stack, context, dictionary);
     }
+    Ok(())
 }
 
 /// Function used to process a sequence of tokens received by parameter as a reference list to String values.
 /// It also receives a mutable Stack and WordsDictionary to change the values if necessary by passing to other functions.
+/// Every token is read from a new ReadingState and ForthContext (which prints to the standard output), so the tokens must contain complete definitions and control structures,
+/// and the variables they define can only be used by them.
+/// Returns the first error found, or the error of a definition or loop left open.
+pub fn read_tokens(
+    tokens: &[String],
+    stack: &mut Stack,
+    dictionary: &mut WordsDictionary,
+) -> Result<(), ForthError> {
+    let mut state = ReadingState::new();
+    let mut context = ForthContext::default();
+    read_tokens_with_state(tokens, stack, &mut context, dictionary, &mut state)?;
+    state.finish()
+}
+
+/// Function used to process a sequence of tokens received by parameter as a reference list to String values, continuing the ReadingState left by the previous ones.
+/// It also receives a mutable Stack, ForthContext and WordsDictionary to change the values if necessary by passing to other functions.
 /// This function is the core interpreter that:
 /// 1. Manages word definition mode (between `:` and `;`)
 /// 2. Handles execution flow control (if/else/then)
 /// 3. Processes all other operations and literals
-///     NOTE: This function seems too long, but it exceeds 30 lines of body by the way cargo fmt puts line breaks into invoked functions parameters. If we change the parameters to be inline, this would not happen.
-pub fn read_tokens(tokens: &[String], stack: &mut Stack, dictionary: &mut WordsDictionary) {
+///    The processing stops on the first error, which is returned so the caller can decide how to report it. Everything pending in the ReadingState and the return stack is discarded in that case.
+pub fn read_tokens_with_state(
+    tokens: &[String],
+    stack: &mut Stack,
+    context: &mut ForthContext,
+    dictionary: &mut WordsDictionary,
+    state: &mut ReadingState,
+) -> Result<(), ForthError> {
+    let result = process_tokens(tokens, stack, context, dictionary, state);
+    if result.is_err() {
+        state.reset();
+        context.return_stack.truncate(0);
+    }
+    result
+}
+
+fn process_tokens(
+    tokens: &[String],
+    stack: &mut Stack,
+    context: &mut ForthContext,
+    dictionary: &mut WordsDictionary,
+    state: &mut ReadingState,
+) -> Result<(), ForthError> {
     let mut i = 0;
-    let mut flag_defining_word = false;
-    let mut current_word_name = "";
-    let mut current_definition = Vec::new();
-    let mut executed_words = Vec::new();
-    let mut execution_stage_stack = vec![ExecutionStage::Executing];
     while i < tokens.len() {
-        let value = parse_token(&tokens[i], dictionary);
+        let value = match tokens[i].strip_prefix("S\"") {
+            Some(text) => store_string(
+                text,
+                &mut context.memory,
+                state.defining_word || !state.definition.is_empty(),
+            )?,
+            None => parse_token(&tokens[i], dictionary, stack.cell(), context.memory.base())?,
+        };
         match &value {
-            ForthValue::Word(ForthWord::Definition) => {
-                handle_word_definition(
-                    tokens,
-                    &mut i,
-                    &mut flag_defining_word,
-                    &mut current_word_name,
-                    &mut current_definition,
-                );
-                if !flag_defining_word {
-                    return;
-                }
+            ForthValue::Word(ForthWord::Definition) => handle_word_definition(
+                tokens,
+                &mut i,
+                &mut state.defining_word,
+                &mut state.word_name,
+                &mut state.definition,
+            )?,
+            ForthValue::Word(ForthWord::End) => handle_word_end(
+                &mut state.defining_word,
+                &state.word_name,
+                &mut state.definition,
+                dictionary,
+                state.allow_self_calls,
+            )?,
+            ForthValue::Word(ForthWord::Variable) => handle_variable_definition(
+                tokens,
+                &mut i,
+                state.defining_word,
+                &mut context.memory,
+                dictionary,
+                1,
+            )?,
+            ForthValue::Word(ForthWord::TwoVariable) => handle_variable_definition(
+                tokens,
+                &mut i,
+                state.defining_word,
+                &mut context.memory,
+                dictionary,
+                2,
+            )?,
+            ForthValue::Word(ForthWord::Constant) => {
+                handle_constant_definition(tokens, &mut i, state.defining_word, stack, dictionary)?
             }
-            ForthValue::Word(ForthWord::End) => {
-                handle_word_end(
-                    &mut flag_defining_word,
-                    current_word_name,
-                    &mut current_definition,
-                    dictionary,
-                );
-                if flag_defining_word {
-                    flag_defining_word = false;
-                } else {
-                    return;
-                }
+            ForthValue::Word(ForthWord::Include) => {
+                handle_include(tokens, &mut i, false, stack, context, dictionary, state)?
             }
-            _ => {
-                handle_other_token(
-                    value,
-                    flag_defining_word,
-                    &mut current_definition,
-                    stack,
-                    dictionary,
-                    &mut executed_words,
-                    &mut execution_stage_stack,
-                );
+            ForthValue::Word(ForthWord::Require) => {
+                handle_include(tokens, &mut i, true, stack, context, dictionary, state)?
             }
+            _ => handle_other_token(value, state, stack, context, dictionary)?,
         }
         i += 1;
     }
-    if flag_defining_word {
-        print_error(ForthError::InvalidWord);
+    Ok(())
+}
+
+/// Function that handles the inclusion of a source file ('INCLUDE path', or 'REQUIRE path' if once is true, which does nothing if the file was already loaded).
+/// The path is the token after the word (in the reference list of String received by parameter, at the mutable index i), and it is relative to the directory of the file being read,
+/// or to the root directory of the files (file_root) if no file is being read. It can also be absolute or have '..', since the source files are not kept inside that directory as the files opened by the program.
+/// The lines of the file are read with the same Stack, ForthContext, WordsDictionary and ReadingState, and the file must be complete (see finish).
+/// Returns invalid-word if a word or a control structure is being defined or there is no path, a generic error if the file can not be read,
+/// include-cycle if the file is already being read, or the error found in the file with its path and line.
+fn handle_include(
+    tokens: &[String],
+    i: &mut usize,
+    once: bool,
+    stack: &mut Stack,
+    context: &mut ForthContext,
+    dictionary: &mut WordsDictionary,
+    state: &mut ReadingState,
+) -> Result<(), ForthError> {
+    if state.defining_word || !state.definition.is_empty() {
+        return Err(ForthError::InvalidWord);
+    }
+    *i += 1;
+    let name = tokens.get(*i).ok_or(ForthError::InvalidWord)?;
+    let directory = match state.sources.last().and_then(|source| source.parent()) {
+        Some(directory) => directory,
+        None => context.files.root(),
+    };
+    let path = directory.join(name);
+    let display = path.display().to_string();
+    let lines = read_file(&display)
+        .map_err(|_| ForthError::Generic(format!("Impossible to read {display}")))?;
+    let canonical = fs::canonicalize(&path).unwrap_or(path);
+    if state.sources.contains(&canonical) {
+        return Err(ForthError::IncludeCycle(display));
+    }
+    if once && state.included.contains(&canonical) {
+        return Ok(());
+    }
+    state.sources.push(canonical);
+    let result = include_lines(&lines, stack, context, dictionary, state)
+        .map_err(|(line, error)| ForthError::InFile(display, line, Box::new(error)));
+    state.pop_source(result.is_ok());
+    result
+}
+
+/// Function that reads the lines of an included source file received by parameter, continuing the ReadingState received by parameter, and checks that the file is complete.
+/// Returns the number of the line (starting at 1) and the first error found in it, if any.
+fn include_lines(
+    lines: &[String],
+    stack: &mut Stack,
+    context: &mut ForthContext,
+    dictionary: &mut WordsDictionary,
+    state: &mut ReadingState,
+) -> Result<(), (usize, ForthError)> {
+    for (index, line) in lines.iter().enumerate() {
+        let tokens = tokenize_with_state(line, state);
+        process_tokens(&tokens, stack, context, dictionary, state)
+            .map_err(|error| (index + 1, error))?;
     }
+    state.finish().map_err(|error| (lines.len(), error))
 }
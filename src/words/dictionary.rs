@@ -1,12 +1,22 @@
-use crate::forth_value::ForthValue;
+use crate::bytecode::Instruction;
 use std::collections::HashMap;
 
+/// This struct represents a definition of a word. It contains the name of the word and its body, compiled as a vector of Instruction.
+#[derive(Debug)]
+pub struct WordDefinition {
+    pub name: String,
+    pub body: Vec<Instruction>,
+}
+
 /// This struct is used to handle the words defined in the program.
-/// It contains words as field, which is a HashMap where the key is a String (word-name) and the value (word-body) is represented by a vector of ForthValue
+/// It contains the definitions in the order they were added (so a compiled body can refer to one by its index), and words as field,
+/// which is a HashMap where the key is a String (word-name) and the value is the index of its current definition.
+/// Redefining a word adds a new definition, so the bodies that refer to the previous one keep using it.
 ///
 #[derive(Debug, Default)]
 pub struct WordsDictionary {
-    words: HashMap<String, Vec<ForthValue>>,
+    definitions: Vec<WordDefinition>,
+    words: HashMap<String, usize>,
 }
 
 impl WordsDictionary {
@@ -15,22 +25,29 @@ impl WordsDictionary {
         Self::default()
     }
 
-    /// Function used to add a word to the dictionary. It receives a mutable instance of this, the name (key) of the word as &str, and a definition that is, as said, a vector of ForthValue.
-    /// Inserts the key as uppercase to be insensitive case
-    pub fn add_word(&mut self, name: &str, definition: Vec<ForthValue>) {
-        self.words.insert(name.to_uppercase(), definition);
+    /// Function used to add a word to the dictionary. It receives a mutable instance of this, the name (key) of the word as &str, and its body, that is, as said, a vector of Instruction.
+    /// Inserts the key as uppercase to be insensitive case. Returns the index of the new definition.
+    pub fn add_word(&mut self, name: &str, body: Vec<Instruction>) -> usize {
+        let name = name.to_uppercase();
+        let index = self.definitions.len();
+        self.words.insert(name.to_string(), index);
+        self.definitions.push(WordDefinition { name, body });
+        index
+    }
+
+    /// Function used to get the index of the current definition of a word. It receives an instance of this and the name (key) of the word as &str.
+    pub fn find_word(&self, name: &str) -> Option<usize> {
+        self.words.get(name).copied()
     }
 
-    /// Function used to get a word of the current dictionary. It receives an instance of this and the name (key) of the word as &str.
-    /// Gets the key as a reference to the current value (vector of ForthValue).
-    pub fn get_word(&self, name: &str) -> Option<&Vec<ForthValue>> {
-        self.words.get(name)
+    /// Function used to get a definition of the dictionary by the index received by parameter.
+    pub fn get_definition(&self, index: usize) -> Option<&WordDefinition> {
+        self.definitions.get(index)
     }
 
-    /// Function used to get a mutable word instance of the dictionary. It receives a mutable instance of this and the name (key) of the word as &str.
-    /// Gets the key as a mutable reference to the current value (vector of ForthValue).
-    pub fn get_word_mut(&mut self, name: &str) -> Option<&mut Vec<ForthValue>> {
-        self.words.get_mut(name)
+    /// Function that returns the index the next definition added to the dictionary will have (used to compile recursive calls).
+    pub fn next_index(&self) -> usize {
+        self.definitions.len()
     }
 
     /// Function used to know if a word is or is not defined. It receives a reference to the dictionary and the name (key) of the word as &str.
@@ -1,10 +1,13 @@
 use super::dictionary::WordsDictionary;
 use crate::{
-    errors::{ForthError, print_error},
+    bytecode::{Instruction, compile_definition},
+    cell::Cell,
+    context::ForthContext,
+    data_memory::DataMemory,
+    errors::ForthError,
     forth_value::ForthValue,
-    other_executions::{ExecutionStage, execute_instruction},
+    other_executions::execute_definition,
     stack::Stack,
-    utils::get_copy_forth_value,
 };
 
 /// Enum that represents the word modes that can be interpreted by the program.
@@ -14,12 +17,37 @@ use crate::{
 /// - Start: tuple that contains a String value that indicates its word-name. This is next to ':'
 /// - Definition: the current word is being defined.
 /// - End: End of the word, which in Forth is defined with ';'
+/// - Variable: defines a word that pushes the address of a new cell of the data memory ('VARIABLE name').
+/// - TwoVariable: defines a word that pushes the address of two new cells of the data memory, to keep a double-cell number ('2VARIABLE name').
+/// - Constant: defines a word that pushes the value on top of the stack ('CONSTANT name').
+/// - Recurse: calls the word being defined ('RECURSE'). It is compiled as a call to the new definition when the definition ends.
+/// - Include: reads and interprets a source file ('INCLUDE path').
+/// - Require: reads and interprets a source file, unless it was already loaded ('REQUIRE path').
 ///
 #[derive(Debug)]
 pub enum ForthWord {
     Start(String),
     Definition,
     End,
+    Variable,
+    TwoVariable,
+    Constant,
+    Recurse,
+    Include,
+    Require,
+}
+
+/// Function that returns the name of the word being defined, which is the token next to the index (i) received by parameter, advancing the index to it.
+/// Returns invalid-word if the name is missing or is a number.
+fn read_word_name(tokens: &[String], i: &mut usize) -> Result<String, ForthError> {
+    let word_name = tokens.get(*i + 1).ok_or(ForthError::InvalidWord)?;
+
+    if word_name.parse::<i128>().is_ok() {
+        return Err(ForthError::InvalidWord);
+    }
+
+    *i += 1;
+    Ok(word_name.to_uppercase())
 }
 
 /// Function that handles the start of a word definition ':'.
@@ -27,121 +55,141 @@ pub enum ForthWord {
 /// 1. Validating the definition context
 /// 2. Capturing the new word's name
 /// 3. Preparing the definition vector
-///     To do so, receives a reference (by scope) list of String and mutable index (i), flag of definition, the current word name and the values associated in 'definition' (vector of ForthValue)
-pub fn handle_word_definition<'a>(
-    tokens: &'a [String],
+///    To do so, receives a reference list of String and mutable index (i), flag of definition, the current word name and the values associated in 'definition' (vector of ForthValue)
+///    Returns invalid-word if a word is already being defined, or if the name is missing or is a number.
+pub fn handle_word_definition(
+    tokens: &[String],
     i: &mut usize,
     flag: &mut bool,
-    name: &mut &'a str,
+    name: &mut String,
     definition: &mut Vec<ForthValue>,
-) {
+) -> Result<(), ForthError> {
     if *flag {
-        print_error(ForthError::InvalidWord);
-        return;
-    }
-
-    if *i + 1 >= tokens.len() {
-        print_error(ForthError::InvalidWord);
-        return;
+        return Err(ForthError::InvalidWord);
     }
 
-    let word_name = &tokens[*i + 1];
+    *name = read_word_name(tokens, i)?;
+    *flag = true;
+    definition.clear();
+    Ok(())
+}
 
-    if word_name.parse::<i16>().is_ok() {
-        print_error(ForthError::InvalidWord);
-        return;
+/// Function that handles the definition of a variable ('VARIABLE name' or '2VARIABLE name').
+/// It reserves the amount of new cells received by parameter (initialized as 0) in the DataMemory received by parameter and adds the word to the dictionary, whose body pushes the address of the first one.
+///    To do so, receives a reference list of String and mutable index (i), the flag of definition, the data memory, the dictionary and the amount of cells.
+///    Returns invalid-word if a word is being defined or the name is not valid, or the error of reserving the cells.
+pub fn handle_variable_definition(
+    tokens: &[String],
+    i: &mut usize,
+    flag: bool,
+    memory: &mut DataMemory,
+    dictionary: &mut WordsDictionary,
+    cells: Cell,
+) -> Result<(), ForthError> {
+    if flag {
+        return Err(ForthError::InvalidWord);
     }
+    let name = read_word_name(tokens, i)?;
+    let address = memory.here();
+    memory.allot(cells)?;
+    dictionary.add_word(&name, vec![Instruction::Literal(address)]);
+    Ok(())
+}
 
-    *flag = true;
-    *name = word_name;
-    definition.clear();
-    *i += 1;
+/// Function that handles the definition of a constant ('value CONSTANT name').
+/// It pops the value of the Stack received by parameter and adds the word to the dictionary, whose body pushes that value.
+///    To do so, receives a reference list of String and mutable index (i), the flag of definition, the stack and the dictionary.
+///    Returns invalid-word if a word is being defined or the name is not valid, or stack-underflow if there is no value.
+pub fn handle_constant_definition(
+    tokens: &[String],
+    i: &mut usize,
+    flag: bool,
+    stack: &mut Stack,
+    dictionary: &mut WordsDictionary,
+) -> Result<(), ForthError> {
+    if flag {
+        return Err(ForthError::InvalidWord);
+    }
+    let name = read_word_name(tokens, i)?;
+    let value = stack.pop()?;
+    dictionary.add_word(&name, vec![Instruction::Literal(value)]);
+    Ok(())
 }
 
 /// Function that finalizes a word definition when encountering the `;` token.
 /// In order to do that it follows the next sequence:
 /// 1. Validate the definition context
-/// 2. Process the collected definition tokens
-/// 3. Store the final definition in the dictionary
-///     To do so, receives the current word name as &str, a reference mutable flag of definition, the values associated in 'definition' (vector of ForthValue), and a WordsDictionary to make updates and get the words.
+/// 2. Compile the collected definition values into instructions (see compile_definition)
+/// 3. Store the compiled body in the dictionary as the current definition of the word
+///    To do so, receives the current word name as &str, a reference mutable flag of definition, the values associated in 'definition' (vector of ForthValue), and a WordsDictionary to make updates and get the words.
+///    The words used in the definition are called (not copied) when it is executed, so defining words that use other words is cheap.
+///    'RECURSE' is compiled as a recursive call to the word, and so is its own name if allow_self_calls is true (otherwise the name refers to its previous definition, which it must have).
+///    Returns invalid-word if no word is being defined, or the error of compiling the definition.
 pub fn handle_word_end(
     flag: &mut bool,
     name: &str,
     definition: &mut Vec<ForthValue>,
     dictionary: &mut WordsDictionary,
-) {
-    if *flag {
-        let mut new_definition = Vec::with_capacity(definition.len());
-        while let Some(item) = definition.pop() {
-            new_definition.insert(0, item);
-        }
-        let mut final_definition = Vec::with_capacity(new_definition.len());
-        for val in new_definition {
-            if let ForthValue::Word(ForthWord::Start(ref word_name)) = val {
-                if let Some(referenced_definition) = dictionary.get_word(word_name) {
-                    for word_val in referenced_definition {
-                        final_definition.push(get_copy_forth_value(word_val));
-                    }
-                    continue;
-                }
-            }
-            final_definition.push(val);
-        }
-        if dictionary.word_already_defined(name) {
-            if let Some(existing_definition) = dictionary.get_word_mut(name) {
-                *existing_definition = final_definition;
-            }
-        } else {
-            dictionary.add_word(name, final_definition);
-        }
-        *flag = false;
-    } else {
-        print_error(ForthError::InvalidWord);
+    allow_self_calls: bool,
+) -> Result<(), ForthError> {
+    if !*flag {
+        return Err(ForthError::InvalidWord);
     }
+    let values = std::mem::take(definition);
+    let body = compile_definition(values, dictionary, Some(name), allow_self_calls)?;
+    dictionary.add_word(name, body);
+    *flag = false;
+    Ok(())
 }
 
 /// Function that receives a token as &str and returns its corresponding ForthValue if exists.
-/// If token is ':' returns the word mode as definition. If it is ';' returns the word mode as end.
+/// If token is ':' returns the word mode as definition. If it is ';' returns the word mode as end. 'VARIABLE', '2VARIABLE', 'CONSTANT' and 'RECURSE' return their own modes.
 pub fn parse_word(token: &str) -> Option<ForthValue> {
     match token {
         ":" => Some(ForthValue::Word(ForthWord::Definition)),
         ";" => Some(ForthValue::Word(ForthWord::End)),
+        "VARIABLE" => Some(ForthValue::Word(ForthWord::Variable)),
+        "2VARIABLE" => Some(ForthValue::Word(ForthWord::TwoVariable)),
+        "CONSTANT" => Some(ForthValue::Word(ForthWord::Constant)),
+        "RECURSE" => Some(ForthValue::Word(ForthWord::Recurse)),
+        "INCLUDE" => Some(ForthValue::Word(ForthWord::Include)),
+        "REQUIRE" => Some(ForthValue::Word(ForthWord::Require)),
         _ => None,
     }
 }
 
-/// Execute the word if valid. It is also pushed to a vector of String.
-/// The function can execute other words contained in another one, allowing recursion and also redefinition.
+/// Execute the current definition of the word received by parameter, which was not defined when the body that calls it was compiled (so it may call that body back).
+/// Returns unknown-word if the word is not defined, or the error of handle_word_call (call-depth-exceeded stops a recursion without end).
 pub fn handle_word_execution(
-    word_name: &String,
+    word_name: &str,
     stack: &mut Stack,
+    context: &mut ForthContext,
     dictionary: &WordsDictionary,
-    executed_words: &mut Vec<String>,
-) {
-    if executed_words.contains(word_name) {
-        return;
-    }
-
-    executed_words.push(word_name.to_string());
-
-    match dictionary.get_word(word_name) {
-        Some(definition) => {
-            let mut mode_stack = vec![ExecutionStage::Executing];
-            for val in definition {
-                execute_instruction(
-                    val,
-                    stack,
-                    dictionary,
-                    &mut mode_stack,
-                    Some(word_name.to_string()),
-                    executed_words,
-                );
-            }
-        }
-        None => print_error(ForthError::UnknownWord),
-    }
+) -> Result<(), ForthError> {
+    let Some(index) = dictionary.find_word(word_name) else {
+        return Err(ForthError::UnknownWord);
+    };
+    handle_word_call(index, stack, context, dictionary)
+}
 
-    executed_words.pop();
+/// Execute the definition (by its index in the dictionary) received by parameter even if it is already being executed, which allows recursion ('RECURSE').
+/// While its body is executed, a call frame with its index is kept in the return stack.
+/// Returns unknown-word if there is no such definition, call-depth-exceeded if too many words are being executed (for example, a recursion without end),
+/// return-stack-overflow if there is no room for its call frame, or the first error found while executing its body.
+pub fn handle_word_call(
+    index: usize,
+    stack: &mut Stack,
+    context: &mut ForthContext,
+    dictionary: &WordsDictionary,
+) -> Result<(), ForthError> {
+    let Some(definition) = dictionary.get_definition(index) else {
+        return Err(ForthError::UnknownWord);
+    };
+    let base = context.return_stack.len();
+    context.return_stack.push_call(index)?;
+    let result = execute_definition(&definition.body, stack, context, dictionary);
+    context.return_stack.truncate(base);
+    result
 }
 
 #[cfg(test)]
@@ -157,10 +205,10 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut test_stack = Stack::new(100);
 
-        read_tokens(&tokenize(": foo dup ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": foo dup ;"), &mut test_stack, &mut dict).unwrap();
 
         test_stack.push(1).unwrap();
-        read_tokens(&tokenize("FOO Foo foo"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("FOO Foo foo"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[1, 1, 1, 1]);
     }
@@ -174,10 +222,11 @@ mod tests {
             &tokenize(": dup-twice dup dup ;"),
             &mut test_stack,
             &mut dict,
-        );
+        )
+        .unwrap();
 
         test_stack.push(1).unwrap();
-        read_tokens(&tokenize("dup-twice"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("dup-twice"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[1, 1, 1]);
     }
@@ -187,12 +236,12 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut test_stack = Stack::new(100);
 
-        read_tokens(&tokenize(": foo dup ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": foo dup ;"), &mut test_stack, &mut dict).unwrap();
 
-        read_tokens(&tokenize(": foo dup dup ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": foo dup dup ;"), &mut test_stack, &mut dict).unwrap();
 
         test_stack.push(1).unwrap();
-        read_tokens(&tokenize("foo"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("foo"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[1, 1, 1]);
     }
@@ -206,10 +255,11 @@ mod tests {
             &tokenize(": swap dup dup dup ;"),
             &mut test_stack,
             &mut dict,
-        );
+        )
+        .unwrap();
 
         test_stack.push(1).unwrap();
-        read_tokens(&tokenize("swap"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("swap"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[1, 1, 1, 1]);
     }
@@ -219,13 +269,13 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut test_stack = Stack::new(100);
 
-        read_tokens(&tokenize(": foo 5 ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": foo 5 ;"), &mut test_stack, &mut dict).unwrap();
 
-        read_tokens(&tokenize(": bar foo ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": bar foo ;"), &mut test_stack, &mut dict).unwrap();
 
-        read_tokens(&tokenize(": foo 6 ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": foo 6 ;"), &mut test_stack, &mut dict).unwrap();
 
-        read_tokens(&tokenize("bar foo"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("bar foo"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[5, 6]);
     }
@@ -235,11 +285,11 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut test_stack = Stack::new(100);
 
-        read_tokens(&tokenize(": foo 10 ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": foo 10 ;"), &mut test_stack, &mut dict).unwrap();
 
-        read_tokens(&tokenize(": foo foo 1 + ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": foo foo 1 + ;"), &mut test_stack, &mut dict).unwrap();
 
-        read_tokens(&tokenize("foo"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("foo"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[11]);
     }
@@ -249,9 +299,9 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut test_stack = Stack::new(100);
 
-        read_tokens(&tokenize(": + * ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": + * ;"), &mut test_stack, &mut dict).unwrap();
 
-        read_tokens(&tokenize("3 4 +"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("3 4 +"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[12]);
     }
@@ -261,9 +311,9 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut test_stack = Stack::new(100);
 
-        read_tokens(&tokenize(": countup 1 2 3 ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": countup 1 2 3 ;"), &mut test_stack, &mut dict).unwrap();
 
-        read_tokens(&tokenize("countup"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("countup"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[1, 2, 3]);
     }
@@ -273,10 +323,10 @@ mod tests {
         let mut dict = WordsDictionary::new();
         let mut test_stack = Stack::new(100);
 
-        read_tokens(&tokenize(": swap dup ;"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize(": swap dup ;"), &mut test_stack, &mut dict).unwrap();
 
         test_stack.push(1).unwrap();
-        read_tokens(&tokenize("swap"), &mut test_stack, &mut dict);
+        read_tokens(&tokenize("swap"), &mut test_stack, &mut dict).unwrap();
 
         assert_eq!(test_stack.data, &[1, 1]);
     }
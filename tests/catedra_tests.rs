@@ -0,0 +1,204 @@
+//! Runner of the suites of tests_catedra. Each YAML file is read as a list of cases (name, code, expected_stack, and optionally
+//! expected_output and stack_size), and every case is executed by a new interpreter whose output is kept in memory.
+//! The errors are written to the output as the binary prints them, and the output is compared exactly (only its line endings are normalized).
+
+#[cfg(test)]
+mod tests {
+    use rust_the_forth::{
+        Interpreter, InterpreterConfig, operations::output::OutputMode, output_sink::BufferOutput,
+    };
+    use std::fs;
+
+    const SUITES_PATH: &str = "tests_catedra";
+
+    /// Struct that represents a case of a suite.
+    #[derive(Debug, Default)]
+    struct TestCase {
+        name: String,
+        code: String,
+        expected_stack: Vec<i64>,
+        expected_output: Option<String>,
+        stack_size: Option<usize>,
+    }
+
+    /// Function that reads the text of a double quoted YAML scalar, replacing its escape sequences.
+    fn parse_quoted(value: &str) -> String {
+        let inner = value.trim().trim_start_matches('"').trim_end_matches('"');
+        let mut text = String::new();
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => text.push('\n'),
+                Some('t') => text.push('\t'),
+                Some(other) => text.push(other),
+                None => {}
+            }
+        }
+        text
+    }
+
+    /// Function that reads a YAML flow sequence of numbers (for example, "[1, -2, 3]").
+    fn parse_stack(value: &str) -> Vec<i64> {
+        value
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .filter(|item| !item.trim().is_empty())
+            .map(|item| {
+                item.trim()
+                    .parse()
+                    .expect("invalid number in expected_stack")
+            })
+            .collect()
+    }
+
+    /// Function that reads the cases of a suite. Only the subset of YAML used by tests_catedra is supported:
+    /// a list of maps whose code is a literal block scalar ('|').
+    fn parse_suite(content: &str) -> Vec<TestCase> {
+        let mut cases: Vec<TestCase> = Vec::new();
+        let mut lines = content.lines().peekable();
+        while let Some(line) = lines.next() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = trimmed.strip_prefix("- name:") {
+                cases.push(TestCase {
+                    name: parse_quoted(name),
+                    ..Default::default()
+                });
+                continue;
+            }
+            let Some(case) = cases.last_mut() else {
+                continue;
+            };
+            let Some((key, value)) = trimmed.split_once(':') else {
+                continue;
+            };
+            match key {
+                "code" => {
+                    while let Some(code_line) = lines.peek() {
+                        let indentation = code_line.len() - code_line.trim_start().len();
+                        if !code_line.trim().is_empty() && indentation <= 2 {
+                            break;
+                        }
+                        case.code.push_str(code_line.trim());
+                        case.code.push('\n');
+                        lines.next();
+                    }
+                }
+                "expected_stack" => case.expected_stack = parse_stack(value),
+                "expected_output" => case.expected_output = Some(parse_quoted(value)),
+                "stack_size" => case.stack_size = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+        cases
+    }
+
+    /// Function used to compare outputs written with any line ending ('\r\n' is read as '\n').
+    fn normalize_output(output: &str) -> String {
+        output.replace("\r\n", "\n")
+    }
+
+    /// Function that executes a case line by line (reporting the errors as the binary does), returning a description of the failure, if any.
+    fn run_case(case: &TestCase) -> Result<(), String> {
+        let mut config = InterpreterConfig {
+            output_mode: OutputMode::Separated,
+            ..Default::default()
+        };
+        if let Some(stack_size) = case.stack_size {
+            config.stack_size = stack_size;
+        }
+        let mut forth = Interpreter::new(config);
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        for line in case.code.lines() {
+            if let Err(error) = forth.eval(line) {
+                forth.write_output(&format!("{error}\n")).unwrap();
+            }
+        }
+        if let Err(error) = forth.finish() {
+            forth.write_output(&format!("{error}\n")).unwrap();
+        }
+
+        if forth.stack().data != case.expected_stack {
+            return Err(format!(
+                "expected stack {:?}, got {:?}",
+                case.expected_stack,
+                forth.stack().data
+            ));
+        }
+        let output = forth.output().contents().unwrap_or_default();
+        if let Some(expected_output) = &case.expected_output
+            && normalize_output(expected_output) != normalize_output(output)
+        {
+            return Err(format!(
+                "expected output {expected_output:?}, got {output:?}"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Function that runs every case of the suite received by parameter, printing the result of each one, and returns the names of the cases that failed.
+    fn run_suite(file_name: &str) -> Vec<String> {
+        let path = format!("{SUITES_PATH}/{file_name}");
+        let content = fs::read_to_string(&path).expect("impossible to read the suite");
+        let cases = parse_suite(&content);
+        assert!(!cases.is_empty(), "{path} has no cases");
+
+        let mut failed = Vec::new();
+        for case in &cases {
+            match run_case(case) {
+                Ok(()) => println!("[PASS] {}", case.name),
+                Err(reason) => {
+                    println!("[FAIL] {}: {reason}", case.name);
+                    failed.push(format!("{path}: {}", case.name));
+                }
+            }
+        }
+        failed
+    }
+
+    #[test]
+    fn test_parse_suite() {
+        let cases = parse_suite(
+            "# comment\n- name: \"first\"\n  code: |\n    1 2\n    +\n  expected_output: \"a\\n\"\n  expected_stack: [3]\n\n- name: \"second\"\n  stack_size: 10\n  code: |\n    -1\n  expected_stack: [-1]\n",
+        );
+
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].name, "first");
+        assert_eq!(cases[0].code, "1 2\n+\n");
+        assert_eq!(cases[0].expected_output.as_deref(), Some("a\n"));
+        assert_eq!(cases[0].expected_stack, &[3]);
+        assert_eq!(cases[1].stack_size, Some(10));
+        assert_eq!(cases[1].expected_stack, &[-1]);
+    }
+
+    #[test]
+    fn test_catedra_suites() {
+        let mut suites: Vec<String> = fs::read_dir(SUITES_PATH)
+            .expect("impossible to read the suites directory")
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|file_name| file_name.ends_with(".yaml"))
+            .collect();
+        suites.sort();
+        assert!(!suites.is_empty(), "{SUITES_PATH} has no suites");
+
+        let mut failed = Vec::new();
+        for suite in &suites {
+            println!("----- {suite} -----");
+            failed.extend(run_suite(suite));
+        }
+        assert!(
+            failed.is_empty(),
+            "{} cases failed: {failed:#?}",
+            failed.len()
+        );
+    }
+}
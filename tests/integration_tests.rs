@@ -1,357 +1,521 @@
 #[cfg(test)]
 mod tests {
     use rust_the_forth::{
-        stack::Stack,
-        tokens::{read_tokens, tokenize},
-        words::dictionary::WordsDictionary,
+        ForthError, Interpreter, InterpreterConfig, input_source::BufferInput,
+        operations::output::OutputMode, output_sink::BufferOutput,
     };
-    const TEST_STACK_SIZE: usize = 1024 * 128;
+    use std::{
+        io::{Read, Write},
+        process::{Command, Stdio},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    /// Function that runs the interactive session of the binary with the text received by parameter as its standard input,
+    /// returning what it printed. The session is killed (and the test fails) if it does not end after the input is closed.
+    fn run_repl(input: &str) -> String {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_rust_the_forth"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        let start = Instant::now();
+        while child.try_wait().unwrap().is_none() {
+            if start.elapsed() > Duration::from_secs(10) {
+                child.kill().unwrap();
+                panic!("the session did not end reading {input:?}");
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let mut output = String::new();
+        child
+            .stdout
+            .take()
+            .unwrap()
+            .read_to_string(&mut output)
+            .unwrap();
+        output
+    }
 
     #[test]
     fn test_add_sub() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
+        let mut forth = Interpreter::default();
 
-        read_tokens(&tokenize("1 2 + 4 -"), &mut test_stack, &mut dict);
-        assert_eq!(test_stack.data, &[-1]);
+        forth.eval("1 2 + 4 -").unwrap();
+        assert_eq!(forth.stack().data, &[-1]);
     }
 
     #[test]
     fn test_mul_div() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
+        let mut forth = Interpreter::default();
 
-        read_tokens(&tokenize("2 4 * 3 /"), &mut test_stack, &mut dict);
-        assert_eq!(test_stack.data, &[2]);
+        forth.eval("2 4 * 3 /").unwrap();
+        assert_eq!(forth.stack().data, &[2]);
     }
 
     #[test]
     fn test_mul_add() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
+        let mut forth = Interpreter::default();
 
-        read_tokens(&tokenize("1 3 4 * +"), &mut test_stack, &mut dict);
-        assert_eq!(test_stack.data, &[13]);
+        forth.eval("1 3 4 * +").unwrap();
+        assert_eq!(forth.stack().data, &[13]);
     }
 
     #[test]
     fn test_add_mul() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
+        let mut forth = Interpreter::default();
 
-        read_tokens(&tokenize("1 3 4 + *"), &mut test_stack, &mut dict);
-        assert_eq!(test_stack.data, &[7]);
+        forth.eval("1 3 4 + *").unwrap();
+        assert_eq!(forth.stack().data, &[7]);
     }
 
     #[test]
     fn test_unit_computation_1() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
-
-        read_tokens(&tokenize(": meter 100 * ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": decimeter 10 * ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": centimeter 1 * ;"), &mut test_stack, &mut dict);
-        read_tokens(
-            &tokenize("1 meter 5 decimeter 2 centimeter + +"),
-            &mut test_stack,
-            &mut dict,
-        );
-        assert_eq!(test_stack.data, &[152]);
+        let mut forth = Interpreter::default();
+
+        forth.eval(": meter 100 * ;").unwrap();
+        forth.eval(": decimeter 10 * ;").unwrap();
+        forth.eval(": centimeter 1 * ;").unwrap();
+        forth.eval("1 meter 5 decimeter 2 centimeter + +").unwrap();
+        assert_eq!(forth.stack().data, &[152]);
     }
 
     #[test]
     fn test_unit_computation_2() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
-
-        read_tokens(&tokenize(": seconds 1 * ;"), &mut test_stack, &mut dict);
-        read_tokens(
-            &tokenize(": minutes 60 * seconds ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": hours 60 * minutes ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize("2 hours 13 minutes 5 seconds + +"),
-            &mut test_stack,
-            &mut dict,
-        );
-        assert_eq!(test_stack.data, &[7985]);
+        let mut forth = Interpreter::default();
+
+        forth.eval(": seconds 1 * ;").unwrap();
+        forth.eval(": minutes 60 * seconds ;").unwrap();
+        forth.eval(": hours 60 * minutes ;").unwrap();
+        forth.eval("2 hours 13 minutes 5 seconds + +").unwrap();
+        assert_eq!(forth.stack().data, &[7985]);
     }
 
     #[test]
     fn test_constant_summation() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
-
-        read_tokens(&tokenize(": one1 1 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": one2 one1 one1 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": one4 one2 one2 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": one8 one4 one4 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": one16 one8 one8 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add1 + ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add2 add1 add1 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add4 add2 add2 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add8 add4 add4 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add16 add8 add8 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize("0 one16 add16"), &mut test_stack, &mut dict);
-
-        assert_eq!(test_stack.data, &[16]);
+        let mut forth = Interpreter::default();
+
+        forth.eval(": one1 1 ;").unwrap();
+        forth.eval(": one2 one1 one1 ;").unwrap();
+        forth.eval(": one4 one2 one2 ;").unwrap();
+        forth.eval(": one8 one4 one4 ;").unwrap();
+        forth.eval(": one16 one8 one8 ;").unwrap();
+        forth.eval(": add1 + ;").unwrap();
+        forth.eval(": add2 add1 add1 ;").unwrap();
+        forth.eval(": add4 add2 add2 ;").unwrap();
+        forth.eval(": add8 add4 add4 ;").unwrap();
+        forth.eval(": add16 add8 add8 ;").unwrap();
+        forth.eval("0 one16 add16").unwrap();
+
+        assert_eq!(forth.stack().data, &[16]);
     }
 
     #[test]
     fn test_linear_summation() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
-
-        read_tokens(&tokenize(": next1 dup 1 + ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": one1 1 ;"), &mut test_stack, &mut dict);
-        read_tokens(
-            &tokenize(": next2 next1 next1 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": next4 next2 next2 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": next8 next4 next4 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": next16 next8 next8 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(&tokenize(": add1 + ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add2 add1 add1 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add4 add2 add2 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add8 add4 add4 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add16 add8 add8 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize("0 next16 add16"), &mut test_stack, &mut dict);
-
-        assert_eq!(test_stack.data, &[136]);
+        let mut forth = Interpreter::default();
+
+        forth.eval(": next1 dup 1 + ;").unwrap();
+        forth.eval(": one1 1 ;").unwrap();
+        forth.eval(": next2 next1 next1 ;").unwrap();
+        forth.eval(": next4 next2 next2 ;").unwrap();
+        forth.eval(": next8 next4 next4 ;").unwrap();
+        forth.eval(": next16 next8 next8 ;").unwrap();
+        forth.eval(": add1 + ;").unwrap();
+        forth.eval(": add2 add1 add1 ;").unwrap();
+        forth.eval(": add4 add2 add2 ;").unwrap();
+        forth.eval(": add8 add4 add4 ;").unwrap();
+        forth.eval(": add16 add8 add8 ;").unwrap();
+        forth.eval("0 next16 add16").unwrap();
+
+        assert_eq!(forth.stack().data, &[136]);
     }
 
     #[test]
     fn test_geometric_summation() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
-
-        read_tokens(&tokenize(": next1 dup 2 * ;"), &mut test_stack, &mut dict);
-        read_tokens(
-            &tokenize(": next2 next1 next1 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": next4 next2 next2 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": next8 next4 next4 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(&tokenize(": add1 + ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add2 add1 add1 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add4 add2 add2 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": add8 add4 add4 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize("1 next8 add8"), &mut test_stack, &mut dict);
+        let mut forth = Interpreter::default();
+
+        forth.eval(": next1 dup 2 * ;").unwrap();
+        forth.eval(": next2 next1 next1 ;").unwrap();
+        forth.eval(": next4 next2 next2 ;").unwrap();
+        forth.eval(": next8 next4 next4 ;").unwrap();
+        forth.eval(": add1 + ;").unwrap();
+        forth.eval(": add2 add1 add1 ;").unwrap();
+        forth.eval(": add4 add2 add2 ;").unwrap();
+        forth.eval(": add8 add4 add4 ;").unwrap();
+        forth.eval("1 next8 add8").unwrap();
 
-        assert_eq!(test_stack.data, &[511]);
+        assert_eq!(forth.stack().data, &[511]);
     }
 
     #[test]
     fn test_power_of_2() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
-
-        read_tokens(&tokenize(": next1 dup 2 * ;"), &mut test_stack, &mut dict);
-        read_tokens(
-            &tokenize(": next2 next1 next1 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": next4 next2 next2 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(&tokenize(": mul1 * ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": mul2 mul1 mul1 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize(": mul4 mul2 mul2 ;"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize("1 next4 mul4"), &mut test_stack, &mut dict);
+        let mut forth = Interpreter::default();
+
+        forth.eval(": next1 dup 2 * ;").unwrap();
+        forth.eval(": next2 next1 next1 ;").unwrap();
+        forth.eval(": next4 next2 next2 ;").unwrap();
+        forth.eval(": mul1 * ;").unwrap();
+        forth.eval(": mul2 mul1 mul1 ;").unwrap();
+        forth.eval(": mul4 mul2 mul2 ;").unwrap();
+        forth.eval("1 next4 mul4").unwrap();
 
-        assert_eq!(test_stack.data, &[1024]);
+        assert_eq!(forth.stack().data, &[1024]);
     }
 
     #[test]
     fn test_digit_to_string() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
-
-        read_tokens(
-            &tokenize(
-                ": f dup 0 = if drop .\" zero\" else dup 1 = if drop .\" one\" else dup 2 = if drop .\" two\" then then then ;",
-            ),
-            &mut test_stack,
-            &mut dict,
+        let mut forth = Interpreter::default();
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth.eval(": f dup 0 = if drop .\" zero\" else dup 1 = if drop .\" one\" else dup 2 = if drop .\" two\" then then then ;").unwrap();
+        forth.eval("0 f cr").unwrap();
+        forth.eval("1 f cr").unwrap();
+        forth.eval("2 f cr").unwrap();
+        assert!(forth.stack().data.is_empty());
+        assert_eq!(forth.output().contents(), Some("zero\none\ntwo\n"));
+    }
+
+    #[test]
+    fn test_compatibility_output() {
+        let mut forth = Interpreter::new(InterpreterConfig {
+            output_mode: OutputMode::Compatibility,
+            ..Default::default()
+        });
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth.eval("1 2 3 . . . 65 emit cr .\" done\"").unwrap();
+        assert_eq!(forth.output().contents(), Some("3\n2\n1\nA\n\ndone\n"));
+
+        let mut forth = Interpreter::default();
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth.eval("1 2 3 . . . 65 emit cr .\" done\"").unwrap();
+        assert_eq!(forth.output().contents(), Some("3 2 1 A\ndone"));
+    }
+
+    #[test]
+    fn test_digit_to_string_multi_line() {
+        let mut forth = Interpreter::default();
+
+        forth
+            .eval(
+                ": f
+                  dup 0 = if
+                    drop .\" zero\"
+                  else dup 1 = if
+                    drop .\" one\"
+                  else dup 2 = if
+                    drop .\" two\"
+                  then then then ;
+                0 f cr
+                1 f cr
+                2 f cr",
+            )
+            .unwrap();
+        assert_eq!(forth.finish(), Ok(()));
+        assert!(forth.stack().data.is_empty());
+    }
+
+    #[test]
+    fn test_annotated_source() {
+        let mut forth = Interpreter::default();
+
+        forth
+            .eval(
+                "\\ squares a number
+                : square ( n -- n*n ) dup * ; \\ uses dup
+                ( a comment
+                  spanning lines ) 3 square",
+            )
+            .unwrap();
+        assert_eq!(forth.stack().data, &[9]);
+        assert_eq!(forth.eval("(not-a-comment)"), Err(ForthError::UnknownWord));
+        assert_eq!(forth.finish(), Ok(()));
+
+        forth.eval("4 ( unclosed").unwrap();
+        assert_eq!(forth.finish(), Err(ForthError::InvalidWord));
+        assert_eq!(forth.stack().data, &[9, 4]);
+    }
+
+    #[test]
+    fn test_number_bases() {
+        let mut forth = Interpreter::default();
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth
+            .eval("hex ff 10 + . base @ decimal . $ff #10 %1010 'a'")
+            .unwrap();
+        assert_eq!(forth.stack().data, &[255, 10, 10, 97]);
+        forth
+            .eval("2 base ! 101 . binary base @ octal . 17 .")
+            .unwrap();
+        assert_eq!(forth.stack().data, &[255, 10, 10, 97]);
+        assert_eq!(forth.eval("1 base !"), Err(ForthError::InvalidBase));
+        assert_eq!(forth.output().contents(), Some("10F 16 101 2 17 "));
+    }
+
+    #[test]
+    fn test_double_cells() {
+        let mut forth = Interpreter::default();
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth
+            .eval("2variable total 100000. total 2! total 2@ 50000. d+ total 2! total 2@ d.")
+            .unwrap();
+        forth
+            .eval("1000 1000 m* d. -1. d. hex 10000. decimal d.")
+            .unwrap();
+        forth.eval("70000. 10 um/mod . .").unwrap();
+        assert!(forth.stack().data.is_empty());
+        assert_eq!(
+            forth.output().contents(),
+            Some("150000 1000000 -1 65536 7000 0 ")
         );
-        read_tokens(&tokenize("0 f cr"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize("1 f cr"), &mut test_stack, &mut dict);
-        read_tokens(&tokenize("2 f cr"), &mut test_stack, &mut dict);
-        assert!(test_stack.data.is_empty());
     }
+
+    #[test]
+    fn test_strings() {
+        let mut forth = Interpreter::default();
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth
+            .eval(": greet s\" Hello, World!\" type ; greet")
+            .unwrap();
+        forth
+            .eval("s\" abc\" s\" abd\" compare . s\" abc\" 2dup compare .")
+            .unwrap();
+        forth
+            .eval("s\" hello world\" s\" wor\" search . type")
+            .unwrap();
+        assert!(forth.stack().data.is_empty());
+        assert_eq!(
+            forth.output().contents(),
+            Some("Hello, World!-1 0 -1 world")
+        );
+        assert_eq!(forth.memory().here(), 13);
+
+        let mut forth = Interpreter::new(InterpreterConfig {
+            memory_size: 16,
+            ..Default::default()
+        });
+        forth.set_output(Box::new(BufferOutput::new()));
+        for _ in 0..100 {
+            forth.eval("s\" hello\" 2drop").unwrap();
+        }
+        assert_eq!(forth.memory().here(), 0);
+
+        forth
+            .eval("1 if s\" a\" s\" b\" s\" c\" type type type then")
+            .unwrap();
+        assert_eq!(forth.output().contents(), Some("cba"));
+        assert_eq!(forth.memory().here(), 3);
+    }
+
+    #[test]
+    fn test_input_source() {
+        let mut forth = Interpreter::default();
+        forth.set_output(Box::new(BufferOutput::new()));
+        forth.set_input(Box::new(BufferInput::new("Forth\nab")));
+
+        forth.eval("here 10 allot dup 10 accept type").unwrap();
+        forth
+            .eval(": upper begin key? while key 32 - emit repeat ; upper")
+            .unwrap();
+        forth.eval("key .").unwrap();
+        assert!(forth.stack().data.is_empty());
+        assert_eq!(forth.output().contents(), Some("ForthAB-1 "));
+    }
+
+    #[test]
+    fn test_repl_input() {
+        let output = run_repl("key .\nA\nhere 10 allot dup 10 accept type\nForth\n");
+        assert!(output.contains("65  ok\n"));
+        assert!(output.contains("Forth ok\n"));
+
+        let output = run_repl("key? .\nA\n");
+        assert!(output.contains("-1  ok\n"));
+        let output = run_repl("key? .\n");
+        assert!(output.contains("0  ok\n"));
+    }
+
+    #[test]
+    fn test_file_access() {
+        let root = std::env::temp_dir().join("forth_file_access_test");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("numbers.txt"), "3\n4\n").unwrap();
+        let mut forth = Interpreter::new(InterpreterConfig {
+            file_root: root.to_str().unwrap().to_string(),
+            ..Default::default()
+        });
+        forth.set_output(Box::new(BufferOutput::new()));
+
+        forth
+            .eval("variable fd here 10 allot constant line")
+            .unwrap();
+        forth
+            .eval("s\" numbers.txt\" r/o open-file . fd !")
+            .unwrap();
+        forth
+            .eval(": lines begin line 10 fd @ read-line drop while line swap type repeat drop ;")
+            .unwrap();
+        forth.eval("lines fd @ close-file .").unwrap();
+        forth
+            .eval("s\" out.txt\" w/o create-file . fd ! s\" done\" fd @ write-line . fd @ close-file .")
+            .unwrap();
+        forth
+            .eval("s\" ../numbers.txt\" r/o open-file . .")
+            .unwrap();
+        assert!(forth.stack().data.is_empty());
+        assert_eq!(forth.output().contents(), Some("0 340 0 0 0 -38 0 "));
+        assert_eq!(
+            std::fs::read_to_string(root.join("out.txt")).unwrap(),
+            "done\n"
+        );
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_include_and_require() {
+        let root = std::env::temp_dir().join("forth_include_test");
+        std::fs::create_dir_all(root.join("lib")).unwrap();
+        std::fs::write(root.join("lib/util.fth"), "1 CONSTANT one\n").unwrap();
+        std::fs::write(
+            root.join("lib/math.fth"),
+            "REQUIRE util.fth\n: inc ( n -- n+1 )\n  one + ;\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("main.fth"), "REQUIRE lib/math.fth\n").unwrap();
+        std::fs::write(root.join("a.fth"), "REQUIRE main.fth\nINCLUDE b.fth\n").unwrap();
+        std::fs::write(root.join("b.fth"), "INCLUDE a.fth\n").unwrap();
+        std::fs::write(
+            root.join("broken.fth"),
+            ": twice 2 * ;\ndrop drop\n: half 2 / ;\n",
+        )
+        .unwrap();
+        let path = |name: &str| root.join(name).to_str().unwrap().to_string();
+
+        let mut forth = Interpreter::new(InterpreterConfig {
+            file_root: root.to_str().unwrap().to_string(),
+            ..Default::default()
+        });
+        forth.eval_file(&path("main.fth")).unwrap();
+        forth
+            .eval_from("REQUIRE lib/math.fth 5 inc", &path("main.fth"))
+            .unwrap();
+        assert_eq!(forth.stack().data, &[6]);
+        assert_eq!(forth.dictionary().next_index(), 2);
+
+        assert_eq!(
+            forth.eval_file(&path("a.fth")),
+            Err(ForthError::InFile(
+                path("b.fth"),
+                1,
+                Box::new(ForthError::IncludeCycle(path("a.fth")))
+            ))
+        );
+        assert_eq!(
+            forth.eval("INCLUDE broken.fth"),
+            Err(ForthError::InFile(
+                path("broken.fth"),
+                2,
+                Box::new(ForthError::StackUnderflow)
+            ))
+        );
+        assert_eq!(
+            forth.eval_from("REQUIRE broken.fth 4 half", &path("main.fth")),
+            Err(ForthError::InFile(
+                path("broken.fth"),
+                2,
+                Box::new(ForthError::StackUnderflow)
+            ))
+        );
+        assert_eq!(
+            forth.eval("INCLUDE missing.fth").unwrap_err().to_string(),
+            format!("[ERROR]: Impossible to read {}", path("missing.fth"))
+        );
+        std::fs::write(root.join("common.fth"), "7\n").unwrap();
+        std::fs::write(root.join("lib/parent.fth"), "INCLUDE ../common.fth\n").unwrap();
+        forth.eval("INCLUDE lib/parent.fth").unwrap();
+        forth
+            .eval(&format!("INCLUDE {}", path("common.fth")))
+            .unwrap();
+        assert_eq!(forth.stack().data, &[7, 7]);
+
+        std::fs::write(
+            root.join("flaky.fth"),
+            "1 CONSTANT first\nready\n2 CONSTANT second\n",
+        )
+        .unwrap();
+        let mut errors = Vec::new();
+        forth
+            .eval_file_reporting(&path("flaky.fth"), |error| errors.push(error))
+            .unwrap();
+        assert_eq!(errors, &[ForthError::UnknownWord]);
+        assert_eq!(
+            forth.eval_file(&path("flaky.fth")),
+            Err(ForthError::UnknownWord)
+        );
+        forth
+            .eval(": ready 3 ; REQUIRE flaky.fth REQUIRE flaky.fth")
+            .unwrap();
+        assert_eq!(forth.stack().data, &[7, 7, 3]);
+        forth.eval("first second").unwrap();
+        assert_eq!(forth.stack().data, &[7, 7, 3, 1, 2]);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
     #[test]
     fn test_heavy_word_definition() {
-        let mut dict = WordsDictionary::new();
-        let mut test_stack = Stack::new(TEST_STACK_SIZE);
-        read_tokens(&tokenize(": word1 1 ;"), &mut test_stack, &mut dict);
-        read_tokens(
-            &tokenize(": word2 word1 word1 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word4 word2 word2 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word8 word4 word4 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word16 word8 word8 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word32 word16 word16 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word64 word32 word32 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word128 word64 word64 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word256 word128 word128 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word512 word256 word256 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word1024 word512 word512 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word2048 word1024 word1024 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word4096 word2048 word2048 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word8192 word4096 word4096 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word16384 word8192 word8192 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word32768 word16384 word16384 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word65536 word32768 word32768 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word131072 word65536 word65536 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word262144 word131072 word131072 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word524288 word262144 word262144 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word1048576 word524288 word524288 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word2097152 word1048576 word1048576 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word4194304 word2097152 word2097152 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word8388608 word4194304 word4194304 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word16777216 word8388608 word8388608 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word33554432 word16777216 word16777216 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word67108864 word33554432 word33554432 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
-        read_tokens(
-            &tokenize(": word134217728 word67108864 word67108864 ;"),
-            &mut test_stack,
-            &mut dict,
-        );
+        let mut forth = Interpreter::default();
+        forth.eval(": word1 1 ;").unwrap();
+        forth.eval(": word2 word1 word1 ;").unwrap();
+        forth.eval(": word4 word2 word2 ;").unwrap();
+        forth.eval(": word8 word4 word4 ;").unwrap();
+        forth.eval(": word16 word8 word8 ;").unwrap();
+        forth.eval(": word32 word16 word16 ;").unwrap();
+        forth.eval(": word64 word32 word32 ;").unwrap();
+        forth.eval(": word128 word64 word64 ;").unwrap();
+        forth.eval(": word256 word128 word128 ;").unwrap();
+        forth.eval(": word512 word256 word256 ;").unwrap();
+        forth.eval(": word1024 word512 word512 ;").unwrap();
+        forth.eval(": word2048 word1024 word1024 ;").unwrap();
+        forth.eval(": word4096 word2048 word2048 ;").unwrap();
+        forth.eval(": word8192 word4096 word4096 ;").unwrap();
+        forth.eval(": word16384 word8192 word8192 ;").unwrap();
+        forth.eval(": word32768 word16384 word16384 ;").unwrap();
+        forth.eval(": word65536 word32768 word32768 ;").unwrap();
+        forth.eval(": word131072 word65536 word65536 ;").unwrap();
+        forth.eval(": word262144 word131072 word131072 ;").unwrap();
+        forth.eval(": word524288 word262144 word262144 ;").unwrap();
+        forth.eval(": word1048576 word524288 word524288 ;").unwrap();
+        forth
+            .eval(": word2097152 word1048576 word1048576 ;")
+            .unwrap();
+        forth
+            .eval(": word4194304 word2097152 word2097152 ;")
+            .unwrap();
+        forth
+            .eval(": word8388608 word4194304 word4194304 ;")
+            .unwrap();
+        forth
+            .eval(": word16777216 word8388608 word8388608 ;")
+            .unwrap();
+        forth
+            .eval(": word33554432 word16777216 word16777216 ;")
+            .unwrap();
+        forth
+            .eval(": word67108864 word33554432 word33554432 ;")
+            .unwrap();
+        forth
+            .eval(": word134217728 word67108864 word67108864 ;")
+            .unwrap();
 
-        assert!(test_stack.data.is_empty());
+        assert!(forth.stack().data.is_empty());
     }
 }